@@ -0,0 +1,95 @@
+//! Reference signer for the remote keystore of the node.
+//!
+//! Serves the keys of a local keystore over JSON-RPC, on HTTP or on a Unix socket:
+//!
+//! ```bash
+//! node-template-signer --keystore-path /var/lib/signer --unix /run/signer.sock
+//! node-template --validator --keystore-uri unix:///run/signer.sock
+//! ```
+//!
+//! The signer does not authenticate its callers: whoever can reach it can sign with its keys. The
+//! HTTP server therefore only listens on the loopback interface, unless `--unsafe-http-external`
+//! is given, and the Unix socket should only be accessible to the node.
+
+use std::{
+	net::{Ipv4Addr, SocketAddr},
+	path::PathBuf,
+	sync::Arc,
+};
+
+use jsonrpc_core::IoHandler;
+use node_template_keystore::{Signer, SignerApi};
+use sc_keystore::LocalKeystore;
+use sp_core::crypto::key_types::{AURA, GRANDPA};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opties {
+	/// Directory of the keystore. Keys are kept in memory if not given.
+	#[structopt(long, parse(from_os_str))]
+	keystore_path: Option<PathBuf>,
+
+	/// Serve over HTTP on this port of the loopback interface.
+	#[structopt(long, required_unless = "unix", conflicts_with = "unix")]
+	http_port: Option<u16>,
+
+	/// Serve over HTTP on all interfaces instead of only on the loopback interface. Anyone who can
+	/// reach the port can sign with the keys of the signer, so only use this behind a firewall or
+	/// an authenticating proxy.
+	#[structopt(long, requires = "http-port")]
+	unsafe_http_external: bool,
+
+	/// Serve on a Unix socket at this path.
+	#[structopt(long, parse(from_os_str))]
+	unix: Option<PathBuf>,
+
+	/// Add the Aura and Grandpa keys of this secret URI, like `//Alice`, for local testing.
+	#[structopt(long)]
+	dev_seed: Option<String>,
+}
+
+fn main() -> Result<(), String> {
+	let opties = Opties::from_args();
+
+	let keystore: SyncCryptoStorePtr = match &opties.keystore_path {
+		Some(pad) => Arc::new(LocalKeystore::open(pad, None).map_err(|e| e.to_string())?),
+		None => Arc::new(LocalKeystore::in_memory()),
+	};
+
+	if let Some(seed) = &opties.dev_seed {
+		SyncCryptoStore::sr25519_generate_new(&*keystore, AURA, Some(seed))
+			.map_err(|e| e.to_string())?;
+		SyncCryptoStore::ed25519_generate_new(&*keystore, GRANDPA, Some(seed))
+			.map_err(|e| e.to_string())?;
+	}
+
+	let mut io = IoHandler::default();
+	io.extend_with(Signer::new(keystore).to_delegate());
+
+	match (opties.http_port, opties.unix) {
+		(Some(poort), _) => {
+			let ip = if opties.unsafe_http_external {
+				Ipv4Addr::UNSPECIFIED
+			} else {
+				Ipv4Addr::LOCALHOST
+			};
+			let adres = SocketAddr::from((ip, poort));
+			let server = jsonrpc_http_server::ServerBuilder::new(io)
+				.start_http(&adres)
+				.map_err(|e| e.to_string())?;
+			eprintln!("Signer listening on http://{}", server.address());
+			server.wait();
+		},
+		(None, Some(pad)) => {
+			let server = jsonrpc_ipc_server::ServerBuilder::new(io)
+				.start(&pad.to_string_lossy())
+				.map_err(|e| e.to_string())?;
+			eprintln!("Signer listening on unix://{}", pad.display());
+			server.wait();
+		},
+		(None, None) => unreachable!("structopt requires --http-port or --unix"),
+	}
+
+	Ok(())
+}
@@ -0,0 +1,416 @@
+//! A keystore that keeps no keys itself, but asks a signer process over JSON-RPC to sign.
+//!
+//! The signer serves [`SignerApi`] over HTTP or a Unix socket. `node-template-signer` is a
+//! reference signer that serves the keys of a local keystore.
+
+use std::{
+	convert::TryFrom,
+	io::{BufRead, BufReader, Write},
+	os::unix::net::UnixStream,
+	panic,
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use async_trait::async_trait;
+use jsonrpc_core::{ErrorCode, Output};
+use serde_json::{json, Value};
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes,
+};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error, SyncCryptoStore,
+};
+
+mod signer;
+#[cfg(test)]
+mod tests;
+
+use signer::{naar_tekst, uit_tekst};
+pub use signer::{Signer, SignerApi, KEYSTORE_FOUT};
+
+/// How long to wait for the signer to accept a connection and to answer a call. Blocks are signed
+/// within a slot, so a signer that hangs must not stall the node for longer than that.
+const WACHTTIJD: Duration = Duration::from_secs(2);
+
+enum Verbinding {
+	Http(ureq::Agent, String),
+	Unix(PathBuf),
+}
+
+/// A [`SyncCryptoStore`] backed by a remote signer.
+///
+/// Clones share the connection settings. The [`CryptoStore`] methods call the signer on the
+/// blocking thread pool of the Tokio runtime they are polled on.
+#[derive(Clone)]
+pub struct RemoteKeystore {
+	verbinding: Arc<Verbinding>,
+	volgnummer: Arc<AtomicU64>,
+}
+
+impl RemoteKeystore {
+	/// Connects to the signer at `uri`, either `http://host:port` or `unix:///path/to/socket`.
+	///
+	/// Fails if the signer cannot be reached.
+	pub fn open(uri: &str) -> Result<Self, Error> {
+		let verbinding = if let Some(pad) = uri.strip_prefix("unix://") {
+			Verbinding::Unix(pad.into())
+		} else if uri.starts_with("http://") {
+			let agent =
+				ureq::AgentBuilder::new().timeout_connect(WACHTTIJD).timeout(WACHTTIJD).build();
+			Verbinding::Http(agent, uri.into())
+		} else {
+			Err(Error::Other(format!("Unsupported keystore URI {}", uri)))?
+		};
+
+		let keystore =
+			Self { verbinding: Arc::new(verbinding), volgnummer: Arc::new(AtomicU64::new(0)) };
+		keystore.roep::<bool>("signer_hasKeys", json!([[]]))?;
+		Ok(keystore)
+	}
+
+	fn roep<R: serde::de::DeserializeOwned>(
+		&self,
+		methode: &str,
+		params: Value,
+	) -> Result<R, Error> {
+		let verzoek = json!({
+			"jsonrpc": "2.0",
+			"id": self.volgnummer.fetch_add(1, Ordering::Relaxed),
+			"method": methode,
+			"params": params,
+		});
+
+		let antwoord = match &*self.verbinding {
+			Verbinding::Http(agent, url) => agent
+				.post(url)
+				.send_json(verzoek)
+				.map_err(|e| onbereikbaar(methode, e))?
+				.into_string()
+				.map_err(|e| onbereikbaar(methode, e))?,
+			Verbinding::Unix(pad) => {
+				// Connecting to a Unix socket does not wait for the signer to accept, only for room
+				// in its backlog, so only reading and writing can hang.
+				let mut stream = UnixStream::connect(pad).map_err(|e| onbereikbaar(methode, e))?;
+				stream
+					.set_read_timeout(Some(WACHTTIJD))
+					.and_then(|()| stream.set_write_timeout(Some(WACHTTIJD)))
+					.map_err(|e| onbereikbaar(methode, e))?;
+				stream
+					.write_all(verzoek.to_string().as_bytes())
+					.map_err(|e| onbereikbaar(methode, e))?;
+				// Responses on the socket end with a newline.
+				let mut regel = String::new();
+				BufReader::new(stream)
+					.read_line(&mut regel)
+					.map_err(|e| onbereikbaar(methode, e))?;
+				regel
+			},
+		};
+
+		match serde_json::from_str::<Output>(&antwoord) {
+			Ok(Output::Success(succes)) => serde_json::from_value(succes.result)
+				.map_err(|e| Error::Other(format!("Invalid response to {}: {}", methode, e))),
+			Ok(Output::Failure(fout)) if fout.error.code == ErrorCode::ServerError(KEYSTORE_FOUT) =>
+				Err(Error::Other(fout.error.message)),
+			Ok(Output::Failure(fout)) =>
+				Err(Error::Other(format!("{} failed: {}", methode, fout.error.message))),
+			Err(e) => Err(Error::Other(format!("Invalid response to {}: {}", methode, e))),
+		}
+	}
+
+	fn publieke_sleutels<P>(&self, key_type: KeyTypeId, crypto_type: CryptoTypeId) -> Vec<P>
+	where
+		P: for<'a> TryFrom<&'a [u8]>,
+	{
+		match SyncCryptoStore::keys(self, key_type) {
+			Ok(keys) => keys
+				.into_iter()
+				.filter(|key| key.0 == crypto_type)
+				.filter_map(|key| P::try_from(&key.1[..]).ok())
+				.collect(),
+			Err(e) => {
+				log::warn!("Could not list the keys of the remote keystore: {}", e);
+				Vec::new()
+			},
+		}
+	}
+
+	fn genereer<P>(
+		&self,
+		key_type: KeyTypeId,
+		crypto_type: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<P, Error>
+	where
+		P: for<'a> TryFrom<&'a [u8]>,
+	{
+		let public: Bytes = self.roep(
+			"signer_generateNew",
+			json!([naar_tekst(key_type.0), naar_tekst(crypto_type.0), seed]),
+		)?;
+		P::try_from(&public[..]).map_err(|_| Error::Other("Invalid public key".into()))
+	}
+
+	/// Runs `f` on the blocking thread pool, so waiting for the signer does not hold up the other
+	/// tasks of the executor.
+	async fn op_achtergrond<R, F>(&self, f: F) -> R
+	where
+		R: Send + 'static,
+		F: FnOnce(&Self) -> R + Send + 'static,
+	{
+		let keystore = self.clone();
+		tokio::task::spawn_blocking(move || f(&keystore))
+			.await
+			.unwrap_or_else(|e| panic::resume_unwind(e.into_panic()))
+	}
+}
+
+fn onbereikbaar(methode: &str, e: impl std::fmt::Display) -> Error {
+	log::warn!("Remote keystore call {} failed: {}", methode, e);
+	Error::Unavailable
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.publieke_sleutels(id, sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		self.genereer(id, sr25519::CRYPTO_ID, seed)
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.publieke_sleutels(id, ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		self.genereer(id, ed25519::CRYPTO_ID, seed)
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.publieke_sleutels(id, ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		self.genereer(id, ecdsa::CRYPTO_ID, seed)
+	}
+
+	fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.roep(
+			"signer_insertUnknown",
+			json!([naar_tekst(key_type.0), suri, Bytes(public.to_vec())]),
+		)
+		.map_err(|_| ())
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let beschikbaar = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| beschikbaar.contains(key)).collect())
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let keys: Vec<(String, Bytes)> = self.roep("signer_keys", json!([naar_tekst(id.0)]))?;
+
+		keys.into_iter()
+			.map(|(crypto_type, public)| match uit_tekst(&crypto_type) {
+				Some(crypto_type) => Ok(CryptoTypePublicPair(CryptoTypeId(crypto_type), public.0)),
+				None => Err(Error::Other(format!("Invalid crypto type {}", crypto_type))),
+			})
+			.collect()
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let keys: Vec<_> = public_keys
+			.iter()
+			.map(|(public, key_type)| (Bytes(public.clone()), naar_tekst(key_type.0)))
+			.collect();
+
+		self.roep("signer_hasKeys", json!([keys])).unwrap_or(false)
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		let handtekening: Option<Bytes> = self.roep(
+			"signer_signWith",
+			json!([
+				naar_tekst(id.0),
+				naar_tekst(key.0 .0),
+				Bytes(key.1.clone()),
+				Bytes(msg.to_vec())
+			]),
+		)?;
+		Ok(handtekening.map(|handtekening| handtekening.0))
+	}
+
+	/// Not supported: VRF transcripts cannot be sent to the signer. Aura and Grandpa do not need
+	/// them.
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		Err(Error::Other("VRF signing is not supported by the remote keystore".into()))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let handtekening: Option<Bytes> = self.roep(
+			"signer_ecdsaSignPrehashed",
+			json!([naar_tekst(id.0), Bytes(public.as_ref().to_vec()), Bytes(msg.to_vec())]),
+		)?;
+
+		handtekening
+			.map(|handtekening| {
+				ecdsa::Signature::try_from(&handtekening[..])
+					.map_err(|_| Error::Other("Invalid ECDSA signature".into()))
+			})
+			.transpose()
+	}
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.op_achtergrond(move |keystore| SyncCryptoStore::sr25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		let seed = seed.map(str::to_owned);
+		self.op_achtergrond(move |keystore| {
+			SyncCryptoStore::sr25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.op_achtergrond(move |keystore| SyncCryptoStore::ed25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		let seed = seed.map(str::to_owned);
+		self.op_achtergrond(move |keystore| {
+			SyncCryptoStore::ed25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.op_achtergrond(move |keystore| SyncCryptoStore::ecdsa_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		let seed = seed.map(str::to_owned);
+		self.op_achtergrond(move |keystore| {
+			SyncCryptoStore::ecdsa_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let (suri, public) = (suri.to_owned(), public.to_vec());
+		self.op_achtergrond(move |keystore| {
+			SyncCryptoStore::insert_unknown(keystore, id, &suri, &public)
+		})
+		.await
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.op_achtergrond(move |keystore| SyncCryptoStore::supported_keys(keystore, id, keys))
+			.await
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.op_achtergrond(move |keystore| SyncCryptoStore::keys(keystore, id)).await
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys = public_keys.to_vec();
+		self.op_achtergrond(move |keystore| SyncCryptoStore::has_keys(keystore, &public_keys))
+			.await
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		let (key, msg) = (key.clone(), msg.to_vec());
+		self.op_achtergrond(move |keystore| SyncCryptoStore::sign_with(keystore, id, &key, &msg))
+			.await
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		// Fails without calling the signer, so there is nothing to wait for.
+		SyncCryptoStore::sr25519_vrf_sign(self, key_type, public, transcript_data)
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let (public, msg) = (*public, *msg);
+		self.op_achtergrond(move |keystore| {
+			SyncCryptoStore::ecdsa_sign_prehashed(keystore, id, &public, &msg)
+		})
+		.await
+	}
+}
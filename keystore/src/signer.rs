@@ -0,0 +1,186 @@
+//! The JSON-RPC interface of a signer, and a signer that serves it from a local keystore.
+
+use std::convert::TryFrom;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes,
+};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+
+/// Code of the errors the keystore of a signer returns.
+pub const KEYSTORE_FOUT: i64 = 1;
+
+/// Methods a signer serves to a [`RemoteKeystore`](crate::RemoteKeystore).
+///
+/// Key types and crypto types are passed as their four character ids, like `aura` and `sr25`.
+#[rpc(server)]
+pub trait SignerApi {
+	/// The public keys of `key_type`, as `(crypto_type, public)` pairs.
+	#[rpc(name = "signer_keys")]
+	fn keys(&self, key_type: String) -> Result<Vec<(String, Bytes)>>;
+
+	/// Generates a key pair of `crypto_type`, from `seed` if given, and returns its public key.
+	#[rpc(name = "signer_generateNew")]
+	fn generate_new(
+		&self,
+		key_type: String,
+		crypto_type: String,
+		seed: Option<String>,
+	) -> Result<Bytes>;
+
+	/// Stores the key pair of `suri` as a key of `key_type`.
+	#[rpc(name = "signer_insertUnknown")]
+	fn insert_unknown(&self, key_type: String, suri: String, public: Bytes) -> Result<()>;
+
+	/// Whether the private keys of all `(public, key_type)` pairs are available.
+	#[rpc(name = "signer_hasKeys")]
+	fn has_keys(&self, keys: Vec<(Bytes, String)>) -> Result<bool>;
+
+	/// Signs `message` with the key pair of `public`, or returns `None` if it is not available.
+	#[rpc(name = "signer_signWith")]
+	fn sign_with(
+		&self,
+		key_type: String,
+		crypto_type: String,
+		public: Bytes,
+		message: Bytes,
+	) -> Result<Option<Bytes>>;
+
+	/// Signs the 32 byte hash `message` with the ECDSA key pair of `public`.
+	#[rpc(name = "signer_ecdsaSignPrehashed")]
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: String,
+		public: Bytes,
+		message: Bytes,
+	) -> Result<Option<Bytes>>;
+}
+
+/// Serves [`SignerApi`] from a keystore, usually a [`sc_keystore::LocalKeystore`].
+pub struct Signer {
+	keystore: SyncCryptoStorePtr,
+}
+
+impl Signer {
+	/// Creates a signer that signs with the keys in `keystore`.
+	pub fn new(keystore: SyncCryptoStorePtr) -> Self {
+		Self { keystore }
+	}
+}
+
+impl SignerApi for Signer {
+	fn keys(&self, key_type: String) -> Result<Vec<(String, Bytes)>> {
+		let keys = SyncCryptoStore::keys(&*self.keystore, sleuteltype(&key_type)?)
+			.map_err(keystore_fout)?;
+
+		Ok(keys
+			.into_iter()
+			.map(|CryptoTypePublicPair(crypto_type, public)| {
+				(naar_tekst(crypto_type.0), public.into())
+			})
+			.collect())
+	}
+
+	fn generate_new(
+		&self,
+		key_type: String,
+		crypto_type: String,
+		seed: Option<String>,
+	) -> Result<Bytes> {
+		let key_type = sleuteltype(&key_type)?;
+		let seed = seed.as_deref();
+		let keystore = &*self.keystore;
+
+		let public = match cryptotype(&crypto_type)? {
+			sr25519::CRYPTO_ID => SyncCryptoStore::sr25519_generate_new(keystore, key_type, seed)
+				.map(|public| public.as_ref().to_vec()),
+			ed25519::CRYPTO_ID => SyncCryptoStore::ed25519_generate_new(keystore, key_type, seed)
+				.map(|public| public.as_ref().to_vec()),
+			ecdsa::CRYPTO_ID => SyncCryptoStore::ecdsa_generate_new(keystore, key_type, seed)
+				.map(|public| public.as_ref().to_vec()),
+			_ => Err(RpcError::invalid_params(format!("Unknown crypto type {}", crypto_type)))?,
+		};
+
+		Ok(public.map_err(keystore_fout)?.into())
+	}
+
+	fn insert_unknown(&self, key_type: String, suri: String, public: Bytes) -> Result<()> {
+		SyncCryptoStore::insert_unknown(&*self.keystore, sleuteltype(&key_type)?, &suri, &public)
+			.map_err(|()| keystore_fout(sp_keystore::Error::Unavailable))
+	}
+
+	fn has_keys(&self, keys: Vec<(Bytes, String)>) -> Result<bool> {
+		let keys = keys
+			.into_iter()
+			.map(|(public, key_type)| Ok((public.0, sleuteltype(&key_type)?)))
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(SyncCryptoStore::has_keys(&*self.keystore, &keys))
+	}
+
+	fn sign_with(
+		&self,
+		key_type: String,
+		crypto_type: String,
+		public: Bytes,
+		message: Bytes,
+	) -> Result<Option<Bytes>> {
+		let key = CryptoTypePublicPair(cryptotype(&crypto_type)?, public.0);
+
+		SyncCryptoStore::sign_with(&*self.keystore, sleuteltype(&key_type)?, &key, &message)
+			.map(|handtekening| handtekening.map(Into::into))
+			.map_err(keystore_fout)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: String,
+		public: Bytes,
+		message: Bytes,
+	) -> Result<Option<Bytes>> {
+		let public = ecdsa::Public::try_from(&public[..])
+			.map_err(|_| RpcError::invalid_params("Invalid ECDSA public key"))?;
+		let message = <[u8; 32]>::try_from(&message[..])
+			.map_err(|_| RpcError::invalid_params("Message is not a 32 byte hash"))?;
+
+		SyncCryptoStore::ecdsa_sign_prehashed(
+			&*self.keystore,
+			sleuteltype(&key_type)?,
+			&public,
+			&message,
+		)
+		.map(|handtekening| handtekening.map(|handtekening| handtekening.as_ref().to_vec().into()))
+		.map_err(keystore_fout)
+	}
+}
+
+/// Formats a key type or crypto type id for the wire.
+pub(crate) fn naar_tekst(id: [u8; 4]) -> String {
+	String::from_utf8_lossy(&id).into_owned()
+}
+
+/// Parses a key type or crypto type id from the wire.
+pub(crate) fn uit_tekst(tekst: &str) -> Option<[u8; 4]> {
+	<[u8; 4]>::try_from(tekst.as_bytes()).ok()
+}
+
+fn sleuteltype(tekst: &str) -> Result<KeyTypeId> {
+	match uit_tekst(tekst) {
+		Some(id) => Ok(KeyTypeId(id)),
+		None => Err(RpcError::invalid_params(format!("Invalid key type {}", tekst))),
+	}
+}
+
+fn cryptotype(tekst: &str) -> Result<CryptoTypeId> {
+	match uit_tekst(tekst) {
+		Some(id) => Ok(CryptoTypeId(id)),
+		None => Err(RpcError::invalid_params(format!("Invalid crypto type {}", tekst))),
+	}
+}
+
+fn keystore_fout(e: sp_keystore::Error) -> RpcError {
+	RpcError { code: ErrorCode::ServerError(KEYSTORE_FOUT), message: e.to_string(), data: None }
+}
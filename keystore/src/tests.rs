@@ -0,0 +1,105 @@
+use super::*;
+use jsonrpc_core::IoHandler;
+use sc_keystore::LocalKeystore;
+use sp_core::{
+	crypto::key_types::{AURA, GRANDPA},
+	Pair,
+};
+use std::sync::Arc;
+
+fn signer() -> IoHandler {
+	let mut io = IoHandler::default();
+	io.extend_with(Signer::new(Arc::new(LocalKeystore::in_memory())).to_delegate());
+	io
+}
+
+#[test]
+fn signs_over_unix_socket() {
+	let map = tempfile::tempdir().unwrap();
+	let pad = map.path().join("signer.sock");
+	let _server = jsonrpc_ipc_server::ServerBuilder::new(signer())
+		.start(pad.to_str().unwrap())
+		.unwrap();
+	let keystore = RemoteKeystore::open(&format!("unix://{}", pad.display())).unwrap();
+
+	let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice")).unwrap();
+	assert_eq!(public, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+	assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA), vec![public]);
+	assert!(SyncCryptoStore::has_keys(&keystore, &[(public.as_ref().to_vec(), AURA)]));
+	assert!(!SyncCryptoStore::has_keys(&keystore, &[(public.as_ref().to_vec(), GRANDPA)]));
+
+	let handtekening = SyncCryptoStore::sign_with(&keystore, AURA, &public.into(), b"upticket")
+		.unwrap()
+		.unwrap();
+	let handtekening = sr25519::Signature::try_from(&handtekening[..]).unwrap();
+	assert!(sr25519::Pair::verify(&handtekening, b"upticket", &public));
+}
+
+#[test]
+fn signs_over_http() {
+	let server = jsonrpc_http_server::ServerBuilder::new(signer())
+		.start_http(&"127.0.0.1:0".parse().unwrap())
+		.unwrap();
+	let keystore = RemoteKeystore::open(&format!("http://{}", server.address())).unwrap();
+
+	let pair = ed25519::Pair::from_string("//Bob", None).unwrap();
+	assert_eq!(
+		SyncCryptoStore::sign_with(&keystore, GRANDPA, &pair.public().into(), b"upticket").unwrap(),
+		None,
+	);
+
+	SyncCryptoStore::insert_unknown(&keystore, GRANDPA, "//Bob", pair.public().as_ref()).unwrap();
+	assert_eq!(SyncCryptoStore::ed25519_public_keys(&keystore, GRANDPA), vec![pair.public()]);
+
+	let handtekening =
+		SyncCryptoStore::sign_with(&keystore, GRANDPA, &pair.public().into(), b"upticket")
+			.unwrap()
+			.unwrap();
+	assert_eq!(handtekening, pair.sign(b"upticket").as_ref().to_vec());
+}
+
+#[test]
+fn open_fails_without_signer() {
+	let map = tempfile::tempdir().unwrap();
+	let pad = map.path().join("signer.sock");
+
+	assert!(matches!(
+		RemoteKeystore::open(&format!("unix://{}", pad.display())),
+		Err(Error::Unavailable)
+	));
+	assert!(matches!(RemoteKeystore::open("ftp://localhost"), Err(Error::Other(_))));
+}
+
+#[test]
+fn gives_up_on_a_signer_that_does_not_answer() {
+	let map = tempfile::tempdir().unwrap();
+	let pad = map.path().join("signer.sock");
+	let _luisteraar = std::os::unix::net::UnixListener::bind(&pad).unwrap();
+
+	let begin = std::time::Instant::now();
+	assert!(matches!(
+		RemoteKeystore::open(&format!("unix://{}", pad.display())),
+		Err(Error::Unavailable)
+	));
+	assert!(begin.elapsed() < WACHTTIJD * 2);
+}
+
+#[tokio::test]
+async fn signs_asynchronously() {
+	let server = jsonrpc_http_server::ServerBuilder::new(signer())
+		.start_http(&"127.0.0.1:0".parse().unwrap())
+		.unwrap();
+	let keystore = RemoteKeystore::open(&format!("http://{}", server.address())).unwrap();
+
+	let public = CryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice"))
+		.await
+		.unwrap();
+	assert_eq!(CryptoStore::sr25519_public_keys(&keystore, AURA).await, vec![public]);
+
+	let handtekening = CryptoStore::sign_with(&keystore, AURA, &public.into(), b"upticket")
+		.await
+		.unwrap()
+		.unwrap();
+	let handtekening = sr25519::Signature::try_from(&handtekening[..]).unwrap();
+	assert!(sr25519::Pair::verify(&handtekening, b"upticket", &public));
+}
@@ -1,19 +1,46 @@
+use crate::cli::TokenProperties;
+use frame_support::traits::Get;
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	pallet_upticket::{AantalTickets, EvenementId, KlantId, MaxPrijs, Prijs, Tijdstip},
+	AccountId, AuraConfig, Balance, BalancesConfig, CouncilConfig, GenesisConfig, GrandpaConfig,
+	SS58Prefix, Signature, SystemConfig, UpticketModuleConfig, WASM_BINARY,
 };
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
+mod staging;
+
+pub use staging::staging_config;
+
+/// An evenement created at genesis: id, organisator, naam, prijs, max prijs, number of tickets,
+/// begin and end.
+type GenesisEvenement =
+	(EvenementId, AccountId, Vec<u8>, Prijs, MaxPrijs, AantalTickets, Tijdstip, Tijdstip);
+
+/// Begin of the demo evenement, 2029-12-31 20:00 UTC in milliseconds.
+const DEMO_BEGIN: u64 = 1_893_441_600_000;
+/// End of the demo evenement, 2030-01-01 00:00 UTC in milliseconds.
+const DEMO_EINDE: u64 = 1_893_456_000_000;
+
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
+/// The token properties of the runtime, with those in `token` replaced.
+pub fn properties(token: &TokenProperties) -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), "UPT".into());
+	properties.insert("tokenDecimals".into(), 12.into());
+	properties.insert("ss58Format".into(), SS58Prefix::get().into());
+	token.apply(&mut properties);
+	properties
+}
+
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -36,7 +63,7 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+pub fn development_config(token: &TokenProperties) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -50,8 +77,13 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
+				// Organizer of the demo evenement
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Venue council
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -69,13 +101,13 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties(token)),
 		// Extensions
 		None,
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(token: &TokenProperties) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -89,8 +121,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
+				// Organizer of the demo evenement
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Venue council
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -116,7 +154,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(properties(token)),
 		// Extensions
 		None,
 	))
@@ -126,9 +164,31 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
+	organisator: AccountId,
+	council: Vec<AccountId>,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
+) -> GenesisConfig {
+	genesis(
+		wasm_binary,
+		initial_authorities,
+		council,
+		// Configure endowed accounts with initial balance of 1 << 60.
+		endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+		// A demo evenement with two tickets already sold.
+		vec![(1, organisator, b"Demo concert".to_vec(), 10, 20, 100, DEMO_BEGIN, DEMO_EINDE)],
+		vec![(1, 1, false), (1, 2, false)],
+	)
+}
+
+/// The genesis of a chain with the given authorities, council, balances and evenementen.
+fn genesis(
+	wasm_binary: &[u8],
+	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	council: Vec<AccountId>,
+	balances: Vec<(AccountId, Balance)>,
+	evenementen: Vec<GenesisEvenement>,
+	tickets: Vec<(EvenementId, KlantId, bool)>,
 ) -> GenesisConfig {
 	GenesisConfig {
 		system: SystemConfig {
@@ -136,19 +196,15 @@ fn testnet_genesis(
 			code: wasm_binary.to_vec(),
 			changes_trie_config: Default::default(),
 		},
-		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
-		},
+		balances: BalancesConfig { balances },
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
 		},
 		grandpa: GrandpaConfig {
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
 		},
-		sudo: SudoConfig {
-			// Assign network admin rights.
-			key: root_key,
-		},
+		// Administers upticket and, through `Bestuur`, the chain itself through motions.
+		council: CouncilConfig { phantom: Default::default(), members: council },
+		upticket_module: UpticketModuleConfig { evenementen, tickets },
 	}
 }
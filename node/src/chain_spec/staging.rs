@@ -0,0 +1,245 @@
+//! Chain specs for live networks, read from a TOML preset with `--chain staging:<path.toml>`.
+//!
+//! See `node/res/staging.toml` for an example preset.
+
+use std::{fs, path::Path, str::FromStr};
+
+use node_template_runtime::{
+	pallet_upticket::{AantalTickets, EvenementId, KlantId, MaxPrijs, Prijs, Tijdstip},
+	AccountId, Balance, WASM_BINARY,
+};
+use sc_service::{config::MultiaddrWithPeerId, ChainType, Properties};
+use sc_telemetry::TelemetryEndpoints;
+use serde::Deserialize;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::{crypto::Ss58Codec, ed25519, sr25519};
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+
+use super::{genesis, ChainSpec, GenesisEvenement};
+use crate::cli::TokenProperties;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Preset {
+	name: String,
+	id: String,
+	protocol_id: Option<String>,
+	#[serde(default)]
+	boot_nodes: Vec<String>,
+	/// Telemetry endpoints with their verbosity.
+	#[serde(default)]
+	telemetry: Vec<(String, u8)>,
+	/// Chain properties such as `tokenSymbol`, `tokenDecimals` and `ss58Format`.
+	properties: Option<Properties>,
+	/// Members of the venue council, which administers upticket and is the only way to reach
+	/// Root.
+	council: Vec<String>,
+	authorities: Vec<Authority>,
+	#[serde(default)]
+	endowed: Vec<Endowment>,
+	#[serde(default)]
+	evenementen: Vec<Evenement>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Authority {
+	aura: String,
+	grandpa: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Endowment {
+	account: String,
+	balance: Bedrag,
+}
+
+/// A balance, as a string if it does not fit a TOML integer.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Bedrag {
+	Getal(u64),
+	Tekst(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Evenement {
+	id: EvenementId,
+	organisator: String,
+	naam: String,
+	prijs: Prijs,
+	max_prijs: MaxPrijs,
+	aantal_tickets: AantalTickets,
+	/// Begin of the evenement, in milliseconds since the Unix epoch.
+	begin: Tijdstip,
+	/// End of the evenement, in milliseconds since the Unix epoch.
+	einde: Tijdstip,
+	/// Klanten that already hold an unscanned ticket.
+	#[serde(default)]
+	tickets: Vec<KlantId>,
+}
+
+/// The genesis described by a [`Preset`], with all keys and amounts parsed.
+#[derive(Clone)]
+struct Genesis {
+	authorities: Vec<(AuraId, GrandpaId)>,
+	council: Vec<AccountId>,
+	balances: Vec<(AccountId, Balance)>,
+	evenementen: Vec<GenesisEvenement>,
+	tickets: Vec<(EvenementId, KlantId, bool)>,
+}
+
+impl Preset {
+	fn lees(toml: &str) -> Result<Self, String> {
+		toml::from_str(toml).map_err(|e| e.to_string())
+	}
+
+	fn genesis(&self) -> Result<Genesis, String> {
+		if self.authorities.is_empty() {
+			Err("A preset needs at least one authority")?
+		}
+		if self.council.is_empty() {
+			Err("A preset needs at least one council member")?
+		}
+
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|authority| {
+				let aura = sr25519::Public::from_ss58check(&authority.aura)
+					.map_err(|e| format!("Invalid Aura key {}: {:?}", authority.aura, e))?;
+				let grandpa = ed25519::Public::from_ss58check(&authority.grandpa)
+					.map_err(|e| format!("Invalid Grandpa key {}: {:?}", authority.grandpa, e))?;
+				Ok((AuraId::from(aura), GrandpaId::from(grandpa)))
+			})
+			.collect::<Result<_, String>>()?;
+
+		let council = self.council.iter().map(|lid| account(lid)).collect::<Result<_, _>>()?;
+
+		let balances = self
+			.endowed
+			.iter()
+			.map(|endowment| {
+				let balance = match &endowment.balance {
+					Bedrag::Getal(balance) => *balance as Balance,
+					Bedrag::Tekst(balance) => balance
+						.replace('_', "")
+						.parse()
+						.map_err(|e| format!("Invalid balance {}: {}", balance, e))?,
+				};
+				Ok((account(&endowment.account)?, balance))
+			})
+			.collect::<Result<_, String>>()?;
+
+		let mut evenementen = Vec::new();
+		let mut tickets = Vec::new();
+		for evenement in &self.evenementen {
+			evenementen.push((
+				evenement.id,
+				account(&evenement.organisator)?,
+				evenement.naam.as_bytes().to_vec(),
+				evenement.prijs,
+				evenement.max_prijs,
+				evenement.aantal_tickets,
+				evenement.begin,
+				evenement.einde,
+			));
+			tickets
+				.extend(evenement.tickets.iter().map(|klant_id| (evenement.id, *klant_id, false)));
+		}
+
+		Ok(Genesis { authorities, council, balances, evenementen, tickets })
+	}
+}
+
+fn account(ss58: &str) -> Result<AccountId, String> {
+	AccountId::from_str(ss58).map_err(|e| format!("Invalid account {}: {}", ss58, e))
+}
+
+/// Reads the preset at `path` into the chain spec of a live network.
+///
+/// Token properties missing from the preset are those of the runtime, and those in `token` replace
+/// both.
+pub fn staging_config(path: &Path, token: &TokenProperties) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Staging wasm not available".to_string())?;
+	let toml = fs::read_to_string(path)
+		.map_err(|e| format!("Could not read preset {}: {}", path.display(), e))?;
+	let preset =
+		Preset::lees(&toml).map_err(|e| format!("Invalid preset {}: {}", path.display(), e))?;
+	let genesis_preset = preset
+		.genesis()
+		.map_err(|e| format!("Invalid preset {}: {}", path.display(), e))?;
+
+	let boot_nodes = preset
+		.boot_nodes
+		.iter()
+		.map(|boot_node| {
+			MultiaddrWithPeerId::from_str(boot_node)
+				.map_err(|e| format!("Invalid boot node {}: {}", boot_node, e))
+		})
+		.collect::<Result<_, _>>()?;
+	let mut properties = super::properties(&Default::default());
+	properties.extend(preset.properties.unwrap_or_default());
+	token.apply(&mut properties);
+	let telemetry = match preset.telemetry {
+		telemetry if telemetry.is_empty() => None,
+		telemetry => Some(TelemetryEndpoints::new(telemetry).map_err(|e| e.to_string())?),
+	};
+
+	Ok(ChainSpec::from_genesis(
+		&preset.name,
+		&preset.id,
+		ChainType::Live,
+		move || {
+			let Genesis { authorities, council, balances, evenementen, tickets } =
+				genesis_preset.clone();
+			genesis(wasm_binary, authorities, council, balances, evenementen, tickets)
+		},
+		boot_nodes,
+		telemetry,
+		preset.protocol_id.as_deref(),
+		Some(properties),
+		None,
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn example_preset_is_valid() {
+		let preset = Preset::lees(include_str!("../../res/staging.toml")).unwrap();
+		let genesis = preset.genesis().unwrap();
+
+		assert_eq!(genesis.authorities.len(), 2);
+		assert_eq!(genesis.council.len(), 2);
+		assert_eq!(genesis.balances[0].1, 1_000_000_000_000_000_000_000);
+		assert_eq!(genesis.tickets, vec![(1, 1, false), (1, 2, false)]);
+	}
+
+	#[test]
+	fn preset_without_authorities_is_invalid() {
+		let preset = Preset::lees(
+			r#"
+			name = "Leeg"
+			id = "leeg"
+			council = ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+			authorities = []
+			"#,
+		)
+		.unwrap();
+
+		assert!(preset.genesis().is_err());
+	}
+
+	#[test]
+	fn preset_without_council_is_invalid() {
+		let mut preset = Preset::lees(include_str!("../../res/staging.toml")).unwrap();
+		preset.council.clear();
+
+		assert!(preset.genesis().is_err());
+	}
+}
@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::{str::FromStr, time::Duration};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +9,98 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	#[structopt(flatten)]
+	pub sealing: SealingParams,
+}
+
+/// How a development node seals its own blocks instead of running Aura and Grandpa.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks on request of the `engine_createBlock` RPC.
+	Manual,
+	/// Seal a block every `--sealing-interval` milliseconds.
+	Interval,
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			"interval" => Ok(Self::Interval),
+			other =>
+				Err(format!("Unknown sealing `{}`, expected instant, manual or interval", other)),
+		}
+	}
+}
+
+#[derive(Debug, Clone, StructOpt)]
+pub struct SealingParams {
+	/// Seal blocks without Aura and Grandpa: `instant`, `manual` or `interval`. Only meant for
+	/// development and CI.
+	#[structopt(long)]
+	pub sealing: Option<Sealing>,
+
+	/// Milliseconds between blocks with `--sealing interval`.
+	#[structopt(long, default_value = "1000")]
+	pub sealing_interval: u64,
+}
+
+impl SealingParams {
+	pub fn interval(&self) -> Duration {
+		Duration::from_millis(self.sealing_interval)
+	}
+}
+
+/// Build a chain specification, with the token properties replaced by those given.
+#[derive(Debug, StructOpt)]
+pub struct BuildSpecCmd {
+	#[structopt(flatten)]
+	pub inner: sc_cli::BuildSpecCmd,
+
+	#[structopt(flatten)]
+	pub token: TokenProperties,
+}
+
+/// Token properties that replace those of a generated chain spec.
+#[derive(Debug, Default, Clone, StructOpt)]
+pub struct TokenProperties {
+	/// Symbol of the native token, like `UPT`.
+	#[structopt(long)]
+	pub token_symbol: Option<String>,
+
+	/// Number of decimals of the native token.
+	#[structopt(long)]
+	pub token_decimals: Option<u8>,
+
+	/// SS58 address format of the chain.
+	#[structopt(long)]
+	pub ss58_format: Option<u16>,
+}
+
+impl TokenProperties {
+	/// Whether no property is replaced.
+	pub fn is_empty(&self) -> bool {
+		self.token_symbol.is_none() && self.token_decimals.is_none() && self.ss58_format.is_none()
+	}
+
+	/// Replaces the given properties in `properties`.
+	pub fn apply(&self, properties: &mut sc_service::Properties) {
+		if let Some(token_symbol) = &self.token_symbol {
+			properties.insert("tokenSymbol".into(), token_symbol.clone().into());
+		}
+		if let Some(token_decimals) = self.token_decimals {
+			properties.insert("tokenDecimals".into(), token_decimals.into());
+		}
+		if let Some(ss58_format) = self.ss58_format {
+			properties.insert("ss58Format".into(), ss58_format.into());
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -15,7 +108,7 @@ pub enum Subcommand {
 	/// Key management cli utilities
 	Key(sc_cli::KeySubcommand),
 	/// Build a chain specification.
-	BuildSpec(sc_cli::BuildSpecCmd),
+	BuildSpec(BuildSpecCmd),
 
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
@@ -38,4 +131,12 @@ pub enum Subcommand {
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
@@ -1,3 +1,5 @@
 pub mod chain_spec;
+pub mod cli;
+pub mod metrics;
 pub mod rpc;
 pub mod service;
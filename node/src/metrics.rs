@@ -0,0 +1,216 @@
+//! Prometheus metrics for the ticketing activity of the upticket pallet.
+//!
+//! The metrics are taken from the events of every new best block, so blocks that are later
+//! retracted are counted as well.
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use node_template_runtime::{
+	opaque::Block, AccountId, BlockNumber, Call, Event, Hash, Runtime, UncheckedExtrinsic,
+};
+use pallet_upticket::EvenementId;
+use pallet_upticket_runtime_api::UpticketApi;
+use sc_client_api::{BlockBackend, BlockchainEvents, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{generic::BlockId, DispatchError};
+use substrate_prometheus_endpoint::{
+	register, CounterVec, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+
+use crate::rpc::upticket::{lees_gebeurtenissen, EventRecord};
+
+/// Ticketing metrics, labelled by evenement.
+pub struct Metrics {
+	verkocht: CounterVec<U64>,
+	gescand: CounterVec<U64>,
+	doorverkocht: CounterVec<U64>,
+	overgedragen: CounterVec<U64>,
+	beschikbaarheid: GaugeVec<U64>,
+	mislukte_scans: CounterVec<U64>,
+}
+
+impl Metrics {
+	/// Registers the metrics in `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			verkocht: register(
+				CounterVec::new(
+					Opts::new("upticket_tickets_sold_total", "Number of tickets bought"),
+					&["evenement"],
+				)?,
+				registry,
+			)?,
+			gescand: register(
+				CounterVec::new(
+					Opts::new("upticket_tickets_scanned_total", "Number of tickets scanned"),
+					&["evenement"],
+				)?,
+				registry,
+			)?,
+			doorverkocht: register(
+				CounterVec::new(
+					Opts::new("upticket_tickets_resold_total", "Number of tickets resold"),
+					&["evenement"],
+				)?,
+				registry,
+			)?,
+			overgedragen: register(
+				CounterVec::new(
+					Opts::new(
+						"upticket_tickets_transferred_total",
+						"Number of tickets given away or transferred by the admin origin",
+					),
+					&["evenement"],
+				)?,
+				registry,
+			)?,
+			beschikbaarheid: register(
+				GaugeVec::new(
+					Opts::new("upticket_tickets_available", "Number of tickets still for sale"),
+					&["evenement"],
+				)?,
+				registry,
+			)?,
+			mislukte_scans: register(
+				CounterVec::new(
+					Opts::new("upticket_scans_failed_total", "Number of failed ticket scans"),
+					&["evenement", "error"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	fn verwerk<C>(&self, client: &C, hash: Hash)
+	where
+		C: BlockBackend<Block> + StorageProvider<Block, sc_service::TFullBackend<Block>>,
+		C: ProvideRuntimeApi<Block>,
+		C::Api: UpticketApi<Block, AccountId, BlockNumber>,
+	{
+		let gebeurtenissen = lees_gebeurtenissen(client, hash);
+		let mut gewijzigd = BTreeSet::new();
+		// Only needed to attribute failed extrinsics, so read on first use.
+		let mut extrinsics = None;
+
+		for EventRecord { phase, event, .. } in gebeurtenissen {
+			match event {
+				Event::UpticketModule(pallet_upticket::Event::EvenementAangemaakt(evenement)) => {
+					gewijzigd.insert(evenement.id);
+				},
+				Event::UpticketModule(pallet_upticket::Event::TicketAangemaakt(id, _, ticket)) => {
+					self.verkocht.with_label_values(&[&id.to_string()]).inc();
+					// Tickets handed out at the gate are scanned without a TicketGescand event.
+					if ticket.is_gescand {
+						self.gescand.with_label_values(&[&id.to_string()]).inc();
+					}
+					gewijzigd.insert(id);
+				},
+				Event::UpticketModule(pallet_upticket::Event::TicketGescand(id, ..)) =>
+					self.gescand.with_label_values(&[&id.to_string()]).inc(),
+				Event::UpticketModule(pallet_upticket::Event::TicketVerkocht(id, ..)) =>
+					self.doorverkocht.with_label_values(&[&id.to_string()]).inc(),
+				Event::UpticketModule(pallet_upticket::Event::TicketGeschonken(id, ..)) |
+				Event::UpticketModule(pallet_upticket::Event::TicketOvergedragen(id, ..)) =>
+					self.overgedragen.with_label_values(&[&id.to_string()]).inc(),
+				Event::UpticketModule(pallet_upticket::Event::EvenementVerwijderd(id)) |
+				Event::UpticketModule(pallet_upticket::Event::EvenementOpgeruimd(id, ..)) |
+				Event::UpticketModule(pallet_upticket::Event::EvenementGeannuleerd(id, ..)) => {
+					// Nothing is for sale any more, so drop the gauge instead of leaving it stale.
+					gewijzigd.remove(&id);
+					let _ = self.beschikbaarheid.remove_label_values(&[&id.to_string()]);
+				},
+				Event::System(frame_system::Event::ExtrinsicFailed(fout, _)) => {
+					let index = match phase {
+						frame_system::Phase::ApplyExtrinsic(index) => index as usize,
+						_ => continue,
+					};
+					let extrinsics =
+						extrinsics.get_or_insert_with(|| lees_extrinsics(client, hash)).as_slice();
+					if let Some(Call::UpticketModule(pallet_upticket::Call::scan_ticket {
+						evenement_id,
+						..
+					})) = extrinsics.get(index).and_then(Option::as_ref).map(|xt| &xt.function)
+					{
+						self.mislukte_scans
+							.with_label_values(&[&evenement_id.to_string(), scanfout(fout)])
+							.inc();
+					}
+				},
+				_ => {},
+			}
+		}
+
+		for id in gewijzigd {
+			self.werk_beschikbaarheid_bij(client, hash, id);
+		}
+	}
+
+	fn werk_beschikbaarheid_bij<C>(&self, client: &C, hash: Hash, id: EvenementId)
+	where
+		C: ProvideRuntimeApi<Block>,
+		C::Api: UpticketApi<Block, AccountId, BlockNumber>,
+	{
+		match client.runtime_api().beschikbaarheid(&BlockId::Hash(hash), id) {
+			Ok(beschikbaar) => self
+				.beschikbaarheid
+				.with_label_values(&[&id.to_string()])
+				.set(beschikbaar.min(u64::MAX as u128) as u64),
+			Err(e) => log::debug!("Could not read the availability of evenement {}: {}", id, e),
+		}
+	}
+}
+
+/// Updates `metrics` for every new best block, until the client shuts down.
+pub async fn run<C>(client: Arc<C>, metrics: Metrics)
+where
+	C: BlockchainEvents<Block> + BlockBackend<Block>,
+	C: StorageProvider<Block, sc_service::TFullBackend<Block>>,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: UpticketApi<Block, AccountId, BlockNumber>,
+{
+	let mut meldingen = client.import_notification_stream();
+	while let Some(melding) = meldingen.next().await {
+		if melding.is_new_best {
+			metrics.verwerk(&*client, melding.hash);
+		}
+	}
+}
+
+/// The extrinsics of block `hash`, by index in the block.
+fn lees_extrinsics<C>(client: &C, hash: Hash) -> Vec<Option<UncheckedExtrinsic>>
+where
+	C: BlockBackend<Block>,
+{
+	match client.block_body(&BlockId::Hash(hash)) {
+		Ok(Some(body)) => body
+			.iter()
+			.map(|xt| UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok())
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+/// The label of the error a scan failed with.
+fn scanfout(fout: DispatchError) -> &'static str {
+	let is = |verwacht: pallet_upticket::Error<Runtime>| {
+		match (DispatchError::from(verwacht), fout) {
+			(
+				DispatchError::Module { index, error, .. },
+				DispatchError::Module { index: fout_index, error: fout_error, .. },
+			) => index == fout_index && error == fout_error,
+			_ => false,
+		}
+	};
+
+	if is(pallet_upticket::Error::TicketAlreadyScanned) {
+		"TicketAlreadyScanned"
+	} else if is(pallet_upticket::Error::TicketNotFound) {
+		"TicketNotFound"
+	} else if is(pallet_upticket::Error::TicketFrozen) {
+		"TicketFrozen"
+	} else {
+		"Other"
+	}
+}
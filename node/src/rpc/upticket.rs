@@ -0,0 +1,282 @@
+//! Subscriptions to the ticket scans and sales of a single evenement.
+//!
+//! The events are read from `System::Events` of each block and decoded with the native runtime
+//! types, so blocks from before a runtime upgrade that changed the events are skipped.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use futures::{future, stream, stream::BoxStream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use node_template_runtime::{opaque::Block, AccountId, Event, Hash};
+use pallet_upticket::{EvenementId, KlantId, Prijs};
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+pub(crate) type EventRecord = frame_system::EventRecord<Event, Hash>;
+
+/// A scanned ticket.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scan {
+	/// Block in which the ticket was scanned.
+	pub block: Hash,
+	/// Owner of the ticket.
+	pub klant_id: KlantId,
+	/// Account that scanned the ticket.
+	pub scanner: AccountId,
+}
+
+/// A ticket that was bought, resold, given away or transferred.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Verkoop {
+	/// Block in which the ticket was sold.
+	pub block: Hash,
+	/// New owner of the ticket.
+	pub klant_id: KlantId,
+	/// Previous owner, if the ticket changed hands.
+	pub vorige_klant_id: Option<KlantId>,
+	/// Price paid, if the ticket was resold.
+	pub prijs: Option<Prijs>,
+}
+
+/// Upticket pub/sub RPC methods.
+#[rpc(server)]
+pub trait UpticketApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Streams the scans of the tickets of `evenement_id`, from finalized blocks only if
+	/// `finalized` is set and from new best blocks otherwise.
+	#[pubsub(subscription = "upticket_scans", subscribe, name = "upticket_subscribeScans")]
+	fn subscribe_scans(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Scan>,
+		evenement_id: EvenementId,
+		finalized: Option<bool>,
+	);
+
+	/// Cancels a scan subscription.
+	#[pubsub(subscription = "upticket_scans", unsubscribe, name = "upticket_unsubscribeScans")]
+	fn unsubscribe_scans(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+
+	/// Streams the ticket sales, resales, gifts and transfers of `evenement_id`, from finalized
+	/// blocks only if `finalized` is set and from new best blocks otherwise.
+	#[pubsub(subscription = "upticket_sales", subscribe, name = "upticket_subscribeSales")]
+	fn subscribe_sales(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Verkoop>,
+		evenement_id: EvenementId,
+		finalized: Option<bool>,
+	);
+
+	/// Cancels a sales subscription.
+	#[pubsub(subscription = "upticket_sales", unsubscribe, name = "upticket_unsubscribeSales")]
+	fn unsubscribe_sales(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// Implements the [`UpticketApi`] RPC trait.
+pub struct Upticket<C> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+}
+
+impl<C> Upticket<C> {
+	/// Creates a new instance of the Upticket RPC handler.
+	pub fn new(client: Arc<C>, manager: SubscriptionManager) -> Self {
+		Self { client, manager }
+	}
+}
+
+impl<C> Upticket<C>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block>,
+	C: StorageProvider<Block, sc_service::TFullBackend<Block>>,
+	C: Send + Sync + 'static,
+{
+	/// The upticket events of every new best or finalized block.
+	fn gebeurtenissen(
+		&self,
+		finalized: bool,
+	) -> BoxStream<'static, (Hash, pallet_upticket::Event<node_template_runtime::Runtime>)> {
+		let client = self.client.clone();
+		let blokken = if finalized {
+			gefinaliseerde_blokken(&self.client)
+		} else {
+			beste_blokken(&self.client)
+		};
+
+		blokken
+			.flat_map(move |hash| {
+				let gebeurtenissen =
+					lees_gebeurtenissen(&*client, hash).into_iter().filter_map(move |record| {
+						match record.event {
+							Event::UpticketModule(gebeurtenis) => Some((hash, gebeurtenis)),
+							_ => None,
+						}
+					});
+				stream::iter(gebeurtenissen)
+			})
+			.boxed()
+	}
+
+	fn voeg_toe<T: Serialize + Send + 'static>(
+		&self,
+		subscriber: Subscriber<T>,
+		stream: BoxStream<'static, T>,
+	) {
+		self.manager.add(subscriber, |sink| {
+			stream
+				.map(|item| Ok::<_, ()>(Ok(item)))
+				.forward(sink.sink_map_err(|e| log::warn!("Error sending notifications: {:?}", e)))
+				.map(|_| ())
+		});
+	}
+}
+
+impl<C> UpticketApi for Upticket<C>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block>,
+	C: StorageProvider<Block, sc_service::TFullBackend<Block>>,
+	C: Send + Sync + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe_scans(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<Scan>,
+		evenement_id: EvenementId,
+		finalized: Option<bool>,
+	) {
+		let scans = self
+			.gebeurtenissen(finalized.unwrap_or(false))
+			.filter_map(move |(block, gebeurtenis)| {
+				future::ready(match gebeurtenis {
+					pallet_upticket::Event::TicketGescand(id, klant_id, scanner)
+						if id == evenement_id =>
+						Some(Scan { block, klant_id, scanner }),
+					_ => None,
+				})
+			})
+			.boxed();
+		self.voeg_toe(subscriber, scans);
+	}
+
+	fn unsubscribe_scans(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+
+	fn subscribe_sales(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<Verkoop>,
+		evenement_id: EvenementId,
+		finalized: Option<bool>,
+	) {
+		let verkopen = self
+			.gebeurtenissen(finalized.unwrap_or(false))
+			.filter_map(move |(block, gebeurtenis)| {
+				future::ready(match gebeurtenis {
+					pallet_upticket::Event::TicketAangemaakt(id, klant_id, _)
+						if id == evenement_id =>
+						Some(Verkoop { block, klant_id, vorige_klant_id: None, prijs: None }),
+					pallet_upticket::Event::TicketVerkocht(id, klant_oud, klant_nieuw, prijs)
+						if id == evenement_id =>
+						Some(Verkoop {
+							block,
+							klant_id: klant_nieuw,
+							vorige_klant_id: Some(klant_oud),
+							prijs: Some(prijs),
+						}),
+					pallet_upticket::Event::TicketGeschonken(id, klant_oud, klant_nieuw) |
+					pallet_upticket::Event::TicketOvergedragen(id, klant_oud, klant_nieuw, _)
+						if id == evenement_id =>
+						Some(Verkoop {
+							block,
+							klant_id: klant_nieuw,
+							vorige_klant_id: Some(klant_oud),
+							prijs: None,
+						}),
+					_ => None,
+				})
+			})
+			.boxed();
+		self.voeg_toe(subscriber, verkopen);
+	}
+
+	fn unsubscribe_sales(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+/// Hashes of the blocks that become the new best block.
+fn beste_blokken<C>(client: &Arc<C>) -> BoxStream<'static, Hash>
+where
+	C: BlockchainEvents<Block>,
+{
+	client
+		.import_notification_stream()
+		.filter_map(|melding| future::ready(melding.is_new_best.then(|| melding.hash)))
+		.boxed()
+}
+
+/// Hashes of all finalized blocks, including those finalized implicitly by a descendant.
+fn gefinaliseerde_blokken<C>(client: &Arc<C>) -> BoxStream<'static, Hash>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	let mut vorige = client.info().finalized_number;
+	let meldingen = client.finality_notification_stream();
+	let client = client.clone();
+
+	meldingen
+		.flat_map(move |melding| {
+			let nummer = *melding.header.number();
+			let hashes: Vec<_> =
+				(vorige + 1..=nummer).filter_map(|n| client.hash(n).ok().flatten()).collect();
+			vorige = nummer;
+			stream::iter(hashes)
+		})
+		.boxed()
+}
+
+/// The events deposited in block `hash`, or none if they cannot be read or decoded.
+pub(crate) fn lees_gebeurtenissen<C>(client: &C, hash: Hash) -> Vec<EventRecord>
+where
+	C: StorageProvider<Block, sc_service::TFullBackend<Block>>,
+{
+	let sleutel = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+
+	match client.storage(&BlockId::Hash(hash), &sleutel) {
+		Ok(Some(data)) => Vec::<EventRecord>::decode(&mut &data.0[..]).unwrap_or_else(|e| {
+			log::debug!("Could not decode the events of block {}: {}", hash, e);
+			Vec::new()
+		}),
+		_ => Vec::new(),
+	}
+}
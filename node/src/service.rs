@@ -1,15 +1,30 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
+use crate::cli::{Sealing, SealingParams};
+use futures::{channel::mpsc, prelude::*};
+use node_template_keystore::RemoteKeystore;
+use node_template_runtime::{self, opaque::Block, Hash, RuntimeApi};
 use sc_client_api::{ExecutorProvider, RemoteBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_manual_seal::{consensus::ConsensusDataProvider, EngineCommand, ManualSealParams};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
-use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool;
+use sp_blockchain::HeaderBackend;
 use sp_consensus::SlotData;
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_consensus_aura::{
+	digests::CompatibleDigestItem,
+	sr25519::{AuthorityPair as AuraPair, AuthoritySignature as AuraSignature},
+	Slot,
+};
+use sp_inherents::InherentData;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, DigestFor, DigestItemFor, Header as HeaderT},
+};
+use sp_timestamp::TimestampInherentData;
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -37,8 +52,20 @@ type FullClient =
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
+/// The consensus specific parts of [`new_partial`].
+pub enum Consensus {
+	/// Aura block authoring with Grandpa finality.
+	AuraGrandpa(
+		sc_finality_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>,
+		sc_finality_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+	),
+	/// The node seals and finalizes its own blocks.
+	ManualSeal(Sealing),
+}
+
 pub fn new_partial(
 	config: &Configuration,
+	sealing: &SealingParams,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -46,23 +73,10 @@ pub fn new_partial(
 		FullSelectChain,
 		sc_consensus::DefaultImportQueue<Block, FullClient>,
 		sc_transaction_pool::FullPool<Block, FullClient>,
-		(
-			sc_finality_grandpa::GrandpaBlockImport<
-				FullBackend,
-				Block,
-				FullClient,
-				FullSelectChain,
-			>,
-			sc_finality_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
-			Option<Telemetry>,
-		),
+		(Consensus, Option<Telemetry>),
 	>,
 	ServiceError,
 > {
-	if config.keystore_remote.is_some() {
-		return Err(ServiceError::Other(format!("Remote Keystores are not supported.")))
-	}
-
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -103,6 +117,25 @@ pub fn new_partial(
 		client.clone(),
 	);
 
+	if let Some(sealing) = sealing.sealing {
+		let import_queue = sc_consensus_manual_seal::import_queue(
+			Box::new(client.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		);
+
+		return Ok(sc_service::PartialComponents {
+			client,
+			backend,
+			task_manager,
+			import_queue,
+			keystore_container,
+			select_chain,
+			transaction_pool,
+			other: (Consensus::ManualSeal(sealing), telemetry),
+		})
+	}
+
 	let (grandpa_block_import, grandpa_link) = sc_finality_grandpa::block_import(
 		client.clone(),
 		&(client.clone() as Arc<_>),
@@ -145,19 +178,19 @@ pub fn new_partial(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (grandpa_block_import, grandpa_link, telemetry),
+		other: (Consensus::AuraGrandpa(grandpa_block_import, grandpa_link), telemetry),
 	})
 }
 
-fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
-	// FIXME: here would the concrete keystore be built,
-	//        must return a concrete type (NOT `LocalKeystore`) that
-	//        implements `CryptoStore` and `SyncCryptoStore`
-	Err("Remote Keystore not supported.")
+fn remote_keystore(url: &String) -> Result<Arc<RemoteKeystore>, sp_keystore::Error> {
+	RemoteKeystore::open(url).map(Arc::new)
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	sealing: &SealingParams,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -166,8 +199,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		mut keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (block_import, grandpa_link, mut telemetry),
-	} = new_partial(&config)?;
+		other: (consensus, mut telemetry),
+	} = new_partial(&config, sealing)?;
 
 	if let Some(url) = &config.keystore_remote {
 		match remote_keystore(url) {
@@ -180,11 +213,9 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		};
 	}
 
-	config.network.extra_sets.push(sc_finality_grandpa::grandpa_peers_set_config());
-	let warp_sync = Arc::new(sc_finality_grandpa::warp_proof::NetworkProvider::new(
-		backend.clone(),
-		grandpa_link.shared_authority_set().clone(),
-	));
+	if let Consensus::AuraGrandpa(..) = consensus {
+		config.network.extra_sets.push(sc_finality_grandpa::grandpa_peers_set_config());
+	}
 
 	let (network, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -195,7 +226,14 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 			import_queue,
 			on_demand: None,
 			block_announce_validator_builder: None,
-			warp_sync: Some(warp_sync),
+			warp_sync: match &consensus {
+				Consensus::AuraGrandpa(_, grandpa_link) =>
+					Some(Arc::new(sc_finality_grandpa::warp_proof::NetworkProvider::new(
+						backend.clone(),
+						grandpa_link.shared_authority_set().clone(),
+					))),
+				Consensus::ManualSeal(_) => None,
+			},
 		})?;
 
 	if config.offchain_worker.enabled {
@@ -214,13 +252,27 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// Lets the `engine_*` RPCs drive manual sealing.
+	let (command_sink, commands_stream) = match consensus {
+		Consensus::ManualSeal(Sealing::Manual) => {
+			let (sink, stream) = mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		},
+		_ => (None, None),
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
 
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+		Box::new(move |deny_unsafe, subscription_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+				subscription_executor,
+			};
 
 			Ok(crate::rpc::create_full(deps))
 		})
@@ -241,6 +293,72 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = crate::metrics::Metrics::register(registry)?;
+		task_manager
+			.spawn_handle()
+			.spawn("upticket-metrics", crate::metrics::run(client.clone(), metrics));
+	}
+
+	let (block_import, grandpa_link) = match consensus {
+		Consensus::AuraGrandpa(block_import, grandpa_link) => (block_import, grandpa_link),
+		Consensus::ManualSeal(mode) => {
+			let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+				task_manager.spawn_handle(),
+				client.clone(),
+				transaction_pool.clone(),
+				prometheus_registry.as_ref(),
+				telemetry.as_ref().map(|x| x.handle()),
+			);
+
+			let seal = |create_empty| EngineCommand::SealNewBlock {
+				create_empty,
+				finalize: true,
+				parent_hash: None,
+				sender: None,
+			};
+			let commands_stream = match mode {
+				Sealing::Manual =>
+					commands_stream.expect("created for manual sealing above").boxed(),
+				Sealing::Instant =>
+					transaction_pool.import_notification_stream().map(move |_| seal(false)).boxed(),
+				Sealing::Interval => {
+					let interval = sealing.interval();
+					stream::unfold((), move |()| async move {
+						futures_timer::Delay::new(interval).await;
+						Some((seal(true), ()))
+					})
+					.boxed()
+				},
+			};
+
+			let slot_duration =
+				sc_consensus_aura::slot_duration(&*client)?.slot_duration().as_millis() as u64;
+			let timestamp_client = client.clone();
+
+			let authorship = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+				block_import: client.clone(),
+				env: proposer_factory,
+				client,
+				pool: transaction_pool.pool().clone(),
+				commands_stream,
+				select_chain,
+				consensus_data_provider: Some(Box::new(AuraDigestProvider { slot_duration })),
+				create_inherent_data_providers: move |parent, ()| {
+					let slot_start = next_slot_start(&*timestamp_client, parent, slot_duration);
+					let timestamp = sealed_timestamp(slot_start);
+					async move { Ok(timestamp) }
+				},
+			});
+
+			// The sealing task is essential, if it fails we take down the service with it.
+			task_manager.spawn_essential_handle().spawn_blocking("manual-seal", authorship);
+
+			network_starter.start_network();
+			return Ok(task_manager)
+		},
+	};
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -336,6 +454,69 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	Ok(task_manager)
 }
 
+/// Puts the Aura slot of the timestamp inherent in the digest of blocks sealed by the node itself,
+/// which the Aura pallet requires.
+struct AuraDigestProvider {
+	slot_duration: u64,
+}
+
+impl ConsensusDataProvider<Block> for AuraDigestProvider {
+	type Transaction = sp_api::TransactionFor<FullClient, Block>;
+
+	fn create_digest(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		inherents: &InherentData,
+	) -> Result<DigestFor<Block>, sc_consensus_manual_seal::Error> {
+		let timestamp = inherents.timestamp_inherent_data().ok().flatten().ok_or_else(|| {
+			sc_consensus_manual_seal::Error::StringError("No timestamp inherent".into())
+		})?;
+		let slot = Slot::from(*timestamp / self.slot_duration);
+
+		let mut digest = DigestFor::<Block>::default();
+		digest.push(
+			<DigestItemFor<Block> as CompatibleDigestItem<AuraSignature>>::aura_pre_digest(slot),
+		);
+		Ok(digest)
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		_params: &mut sc_consensus::BlockImportParams<Block, Self::Transaction>,
+		_inherents: &InherentData,
+	) -> Result<(), sc_consensus_manual_seal::Error> {
+		Ok(())
+	}
+}
+
+/// The start, in milliseconds, of the slot after that of `parent`. Aura rejects two blocks in the
+/// same slot, so the next block may not be stamped before then.
+fn next_slot_start(client: &FullClient, parent: Hash, slot_duration: u64) -> u64 {
+	let parent_slot = client
+		.header(BlockId::Hash(parent))
+		.ok()
+		.flatten()
+		.and_then(|header| {
+			header
+				.digest()
+				.logs()
+				.iter()
+				.find_map(|log| CompatibleDigestItem::<AuraSignature>::as_aura_pre_digest(log))
+		})
+		.map_or(0, |slot| *slot);
+
+	(parent_slot + 1) * slot_duration
+}
+
+/// The current time, or `slot_start` if that is later. Blocks are sealed right away, so every block
+/// sealed in quick succession moves the chain time a slot ahead of the wall clock. The chain time
+/// falls back to the wall clock once sealing pauses for long enough.
+fn sealed_timestamp(slot_start: u64) -> sp_timestamp::InherentDataProvider {
+	let now = *sp_timestamp::Timestamp::current();
+	sp_timestamp::InherentDataProvider::new(now.max(slot_start).into())
+}
+
 /// Builds a new service for a light client.
 pub fn new_light(mut config: Configuration) -> Result<TaskManager, ServiceError> {
 	let telemetry = config
@@ -0,0 +1,70 @@
+//! Lets a collective, such as the venue council, dispatch calls as Root in place of a sudo key.
+//!
+//! Runtime upgrades and changes to the council membership need Root. With this pallet they are
+//! proposed as a motion that calls [`Pallet::dispatch_as_root`], so no single account controls
+//! the chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{DispatchResult, GetDispatchInfo, UnfilteredDispatchable},
+		pallet_prelude::*,
+		weights::Pays,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::boxed::Box;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// A call of the runtime.
+		type Call: Parameter + UnfilteredDispatchable<Origin = Self::Origin> + GetDispatchInfo;
+
+		/// The origin that may dispatch calls as Root.
+		type RootOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was dispatched as Root. \[resultaat\]
+		RootUitgevoerd(DispatchResult),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatches `call` as Root. The result of `call` is only recorded in
+		/// `RootUitgevoerd`, so the motion that dispatched it is executed even if `call` fails.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(dispatch_info.weight.saturating_add(10_000), dispatch_info.class)
+		})]
+		pub fn dispatch_as_root(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			T::RootOrigin::ensure_origin(origin)?;
+
+			let resultaat = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+			Self::deposit_event(Event::RootUitgevoerd(resultaat.map(|_| ()).map_err(|e| e.error)));
+			Ok(Pays::No.into())
+		}
+	}
+}
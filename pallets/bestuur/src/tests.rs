@@ -0,0 +1,46 @@
+use crate::{mock::*, Event as BestuurEvent};
+use frame_support::{assert_noop, assert_ok, storage::unhashed};
+use sp_runtime::DispatchError;
+
+fn zet_opslag(sleutel: &[u8], waarde: &[u8]) -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::set_storage {
+		items: vec![(sleutel.to_vec(), waarde.to_vec())],
+	}))
+}
+
+#[test]
+fn dispatches_calls_of_the_council_as_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bestuur::dispatch_as_root(Origin::signed(RAAD), zet_opslag(b"raad", b"ja")));
+
+		assert_eq!(unhashed::get_raw(b"raad"), Some(b"ja".to_vec()));
+		System::assert_last_event(Event::Bestuur(BestuurEvent::RootUitgevoerd(Ok(()))));
+	});
+}
+
+#[test]
+fn others_cannot_dispatch_as_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bestuur::dispatch_as_root(Origin::signed(ALICE), zet_opslag(b"raad", b"ja")),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Bestuur::dispatch_as_root(Origin::root(), zet_opslag(b"raad", b"ja")),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn records_the_result_of_a_failing_call() {
+	new_test_ext().execute_with(|| {
+		// `remark_with_event` needs a signed origin.
+		let call = Box::new(Call::System(frame_system::Call::remark_with_event { remark: vec![] }));
+		assert_ok!(Bestuur::dispatch_as_root(Origin::signed(RAAD), call));
+
+		System::assert_last_event(Event::Bestuur(BestuurEvent::RootUitgevoerd(Err(
+			DispatchError::BadOrigin,
+		))));
+	});
+}
@@ -0,0 +1,68 @@
+//! Decodes arbitrary bytes into upticket calls, dispatches them against the mock runtime of the
+//! pallet's unit tests and checks the storage invariants of the pallet after every call.
+//!
+//! # Running
+//!
+//! Install `cargo-hfuzz` with `cargo install honggfuzz` and run from this directory:
+//!
+//! ```sh
+//! cargo hfuzz run upticket_call
+//! ```
+//!
+//! Crashing inputs are written to `hfuzz_workspace/upticket_call`. Replay one with:
+//!
+//! ```sh
+//! cargo hfuzz run-debug upticket_call hfuzz_workspace/upticket_call/*.fuzz
+//! ```
+
+use codec::Decode;
+use frame_support::traits::UnfilteredDispatchable;
+use honggfuzz::fuzz;
+use pallet_upticket::mock::*;
+
+/// Senders are Root, the admin origin of the mock, and the accounts `1..SENDERS`. The last of
+/// those, `DAVE`, has no balance.
+const SENDERS: u8 = 5;
+
+fn main() {
+	loop {
+		fuzz!(|data: &[u8]| {
+			voer_uit(data);
+		});
+	}
+}
+
+fn voer_uit(mut data: &[u8]) {
+	new_test_ext().execute_with(|| {
+		// Every step is a sender, a number of seconds to move the clock and a call.
+		while let Ok((afzender, seconden, call)) =
+			<(u8, u16, pallet_upticket::Call<Test>)>::decode(&mut data)
+		{
+			MockTijd::verzet(seconden as u64 * 1_000);
+			let origin = match afzender % SENDERS {
+				0 => Origin::root(),
+				_ if alleen_beheer(&call) => Origin::root(),
+				account => Origin::signed(account as u64),
+			};
+
+			// Errors are expected; only panics and broken invariants are bugs.
+			let _ = call.dispatch_bypass_filter(origin);
+
+			UpticketModule::controleer_integriteit().expect("storage invariants hold");
+		}
+	});
+}
+
+/// Whether only the admin origin can make `call`. These calls always run as Root, so every input
+/// that decodes to one of them gets past the origin check.
+fn alleen_beheer(call: &pallet_upticket::Call<Test>) -> bool {
+	use pallet_upticket::Call;
+	matches!(
+		call,
+		Call::force_transfer_ticket { .. } |
+			Call::force_unscan { .. } |
+			Call::force_cancel_evenement { .. } |
+			Call::ban_organisator { .. } |
+			Call::unban_organisator { .. }
+	)
+}
@@ -0,0 +1,29 @@
+//! Runtime API definition for the upticket pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_upticket::{EvenementId, KlantId, ScanRegistratie};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait UpticketApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The number of scanned tickets of an evenement.
+		fn aanwezigen(evenement_id: EvenementId) -> u128;
+
+		/// The number of tickets of an evenement that are still for sale.
+		fn beschikbaarheid(evenement_id: EvenementId) -> u128;
+
+		/// Whether a ticket is frozen, in which case it cannot be scanned or sold.
+		fn is_bevroren(evenement_id: EvenementId, klant_id: KlantId) -> bool;
+
+		/// The scan log of a single ticket, oldest scan first.
+		fn scan_registraties(
+			evenement_id: EvenementId,
+			klant_id: KlantId,
+		) -> Vec<ScanRegistratie<AccountId, BlockNumber>>;
+	}
+}
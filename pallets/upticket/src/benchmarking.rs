@@ -0,0 +1,347 @@
+//! Benchmarking setup for pallet-upticket
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Upticket;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	sp_runtime::{
+		traits::{Bounded, Hash},
+		Permill,
+	},
+	traits::{Currency, EnsureOrigin, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_std::{convert::TryInto, vec, vec::Vec};
+
+const EVENEMENT: EvenementId = 1;
+const KLANT: KlantId = 1;
+
+/// A whitelisted account with enough balance to pay any deposit.
+fn organisator<T: Config>() -> T::AccountId {
+	let organisator: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&organisator, BalanceOf::<T>::max_value() / 2u32.into());
+	organisator
+}
+
+fn tekst<S: Get<u32>>(lengte: u32) -> BoundedVec<u8, S> {
+	vec![b'x'; lengte as usize].try_into().unwrap()
+}
+
+fn maak_evenement<T: Config>(organisator: &T::AccountId, aantal_tickets: AantalTickets) {
+	Upticket::<T>::create_evenement(
+		RawOrigin::Signed(organisator.clone()).into(),
+		10,
+		20,
+		tekst(T::MaxNaamLengte::get()),
+		EVENEMENT,
+		aantal_tickets,
+		0,
+		0,
+	)
+	.unwrap();
+}
+
+fn koop_ticket<T: Config>(koper: &T::AccountId, klant_id: KlantId) {
+	Upticket::<T>::buy_ticket(
+		RawOrigin::Signed(koper.clone()).into(),
+		EVENEMENT,
+		klant_id,
+		false,
+		None,
+		None,
+	)
+	.unwrap();
+}
+
+/// An evenement with `t` entries to remove, spread evenly over the tickets, scan logs, freezes,
+/// names, presale purchases, redemptions and vouchers of the evenement.
+fn vul_evenement<T: Config>(organisator: &T::AccountId, t: u32) {
+	maak_evenement::<T>(organisator, t.into());
+	for i in 0 .. t {
+		let klant_id: KlantId = (i / 7).into();
+		let code_hash = T::Hashing::hash_of(&klant_id);
+		match i % 7 {
+			0 => koop_ticket::<T>(organisator, klant_id),
+			1 => Upticket::<T>::scan_ticket(
+				RawOrigin::Signed(organisator.clone()).into(),
+				EVENEMENT,
+				klant_id,
+			)
+			.unwrap(),
+			2 => BevrorenTickets::<T>::insert(EVENEMENT, klant_id, T::Hash::default()),
+			3 => TicketNamen::<T>::insert(EVENEMENT, klant_id, T::Hash::default()),
+			4 => VoorverkoopAankopen::<T>::insert(EVENEMENT, account("lid", i, 0), 1),
+			5 => Inwisselingen::<T>::insert(EVENEMENT, code_hash, 1),
+			_ => Vouchers::<T>::insert(EVENEMENT, code_hash, voucher()),
+		}
+	}
+}
+
+fn voucher() -> Voucher {
+	Voucher {
+		korting: Korting::Percentage(Permill::from_percent(20)),
+		max_gebruik: 1_000,
+		verloopt: Tijdstip::max_value(),
+	}
+}
+
+benchmarks! {
+	create_evenement {
+		let n in 1 .. T::MaxNaamLengte::get();
+		let caller = organisator::<T>();
+	}: _(RawOrigin::Signed(caller), 10, 20, tekst(n), EVENEMENT, 1_000, 0, 0)
+	verify {
+		assert!(Evenementen::<T>::contains_key(EVENEMENT));
+	}
+
+	set_evenement_metadata {
+		let b in 0 .. T::MaxTekstLengte::get();
+		let a in 0 .. T::MaxArtiesten::get();
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		let metadata: EvenementMetadataOf<T> = EvenementMetadata {
+			locatie: tekst(b),
+			beschrijving: tekst(b),
+			poster: tekst(b),
+			voorwaarden: tekst(b),
+			artiesten: vec![tekst(b); a as usize].try_into().unwrap(),
+			minimum_leeftijd: Some(18),
+		};
+	}: _(RawOrigin::Signed(caller), EVENEMENT, metadata)
+	verify {
+		assert!(Metadata::<T>::contains_key(EVENEMENT));
+	}
+
+	remove_evenement {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), EVENEMENT)
+	verify {
+		assert!(!Evenementen::<T>::contains_key(EVENEMENT));
+	}
+
+	// The final call, which removes the last `t` entries of every map, pays out the deposit and
+	// removes the evenement itself.
+	purge_evenement {
+		let t in 0 .. T::MaxOpruimPerAanroep::get();
+		let caller = organisator::<T>();
+		vul_evenement::<T>(&caller, t);
+		// The evenement ended at 0.
+		T::BenchmarkTijd::zet(T::OpruimTermijn::get());
+	}: _(RawOrigin::Signed(caller), EVENEMENT)
+	verify {
+		assert!(!Evenementen::<T>::contains_key(EVENEMENT));
+	}
+
+	get_evenement {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), EVENEMENT)
+
+	get_evenement_beschikbaarheid {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), EVENEMENT)
+
+	buy_ticket {
+		let p in 0 .. T::MaxBewijsLengte::get();
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		// Redeeming a voucher with the longest code during a presale for a ticket that is scanned
+		// right away is the most expensive purchase.
+		let code: Code<T> = tekst(T::MaxCodeLengte::get());
+		let code_hash = Upticket::<T>::code_hash(EVENEMENT, &code);
+		Vouchers::<T>::insert(EVENEMENT, code_hash, voucher());
+		let bewijs: Bewijs<T> = (0 .. p)
+			.map(|i| T::Hashing::hash_of(&i))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		let voorverkoop = Voorverkoop {
+			merkle_root: Upticket::<T>::merkle_root(&caller, &bewijs),
+			einde: Tijdstip::max_value(),
+			max_per_lid: 1,
+		};
+		Voorverkopen::<T>::insert(EVENEMENT, voorverkoop);
+	}: _(RawOrigin::Signed(caller.clone()), EVENEMENT, KLANT, true, Some(code), Some(bewijs))
+	verify {
+		assert!(Tickets::<T>::contains_key(EVENEMENT, KLANT));
+		assert_eq!(Inwisselingen::<T>::get(EVENEMENT, code_hash), 1);
+		assert_eq!(VoorverkoopAankopen::<T>::get(EVENEMENT, &caller), 1);
+	}
+
+	scan_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+		// A full scan log forces the oldest scan to be dropped.
+		let registratie = ScanRegistratie {
+			blok: Default::default(),
+			tijdstip: 0,
+			scanner: caller.clone(),
+		};
+		let registraties: BoundedVec<_, T::MaxScanRegistraties> =
+			vec![registratie; T::MaxScanRegistraties::get() as usize].try_into().unwrap();
+		ScanRegistraties::<T>::insert(EVENEMENT, KLANT, registraties);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT)
+	verify {
+		assert_eq!(Aanwezigen::<T>::get(EVENEMENT), 1);
+	}
+
+	sell_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT, KLANT + 1, 20)
+	verify {
+		assert!(Tickets::<T>::contains_key(EVENEMENT, KLANT + 1));
+	}
+
+	// Names must match, so the name of the ticket is read and moved along.
+	transfer_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+		let naam = T::Hash::default();
+		Regels::<T>::insert(EVENEMENT, Overdrachtsregels {
+			sluiting_uren: Some(0),
+			naam_verplicht: true,
+		});
+		TicketNamen::<T>::insert(EVENEMENT, KLANT, naam);
+		Evenementen::<T>::mutate(EVENEMENT, |evenement| evenement.einde = Tijdstip::MAX);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT, KLANT + 1, Some(naam))
+	verify {
+		assert_eq!(TicketNamen::<T>::get(EVENEMENT, KLANT + 1), Some(naam));
+	}
+
+	set_transfer_beleid {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, TransferBeleid::VrijeMarkt)
+	verify {
+		assert_eq!(Evenementen::<T>::get(EVENEMENT).transfer_beleid, TransferBeleid::VrijeMarkt);
+	}
+
+	set_overdrachtsregels {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		let regels = Overdrachtsregels {
+			sluiting_uren: Some(24),
+			naam_verplicht: true,
+		};
+	}: _(RawOrigin::Signed(caller), EVENEMENT, Some(regels))
+	verify {
+		assert!(Regels::<T>::contains_key(EVENEMENT));
+	}
+
+	set_ticket_naam {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT, Some(Default::default()))
+	verify {
+		assert!(TicketNamen::<T>::contains_key(EVENEMENT, KLANT));
+	}
+
+	set_voorverkoop {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		let voorverkoop = Voorverkoop { merkle_root: Default::default(), einde: 0, max_per_lid: 1 };
+	}: _(RawOrigin::Signed(caller), EVENEMENT, Some(voorverkoop))
+	verify {
+		assert!(Voorverkopen::<T>::contains_key(EVENEMENT));
+	}
+
+	add_voucher {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, Default::default(), voucher())
+	verify {
+		assert!(Vouchers::<T>::contains_key(EVENEMENT, T::Hash::default()));
+	}
+
+	remove_voucher {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		Vouchers::<T>::insert(EVENEMENT, T::Hash::default(), voucher());
+		Inwisselingen::<T>::insert(EVENEMENT, T::Hash::default(), 1);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, Default::default())
+	verify {
+		assert!(!Vouchers::<T>::contains_key(EVENEMENT, T::Hash::default()));
+	}
+
+	ban_organisator {
+		let organisator = organisator::<T>();
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, organisator.clone())
+	verify {
+		assert!(GebandeOrganisatoren::<T>::contains_key(&organisator));
+	}
+
+	unban_organisator {
+		let organisator = organisator::<T>();
+		GebandeOrganisatoren::<T>::insert(&organisator, ());
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, organisator.clone())
+	verify {
+		assert!(!GebandeOrganisatoren::<T>::contains_key(&organisator));
+	}
+
+	force_transfer_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+		Upticket::<T>::scan_ticket(RawOrigin::Signed(caller).into(), EVENEMENT, KLANT)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, EVENEMENT, KLANT, KLANT + 1, Default::default())
+	verify {
+		assert!(Tickets::<T>::contains_key(EVENEMENT, KLANT + 1));
+	}
+
+	force_unscan {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+		Upticket::<T>::scan_ticket(RawOrigin::Signed(caller).into(), EVENEMENT, KLANT)?;
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, EVENEMENT, KLANT, Default::default())
+	verify {
+		assert_eq!(Aanwezigen::<T>::get(EVENEMENT), 0);
+	}
+
+	// Measures the final call, like `purge_evenement`.
+	force_cancel_evenement {
+		let t in 0 .. T::MaxOpruimPerAanroep::get();
+		let caller = organisator::<T>();
+		vul_evenement::<T>(&caller, t);
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, EVENEMENT, Default::default())
+	verify {
+		assert!(!Evenementen::<T>::contains_key(EVENEMENT));
+	}
+
+	// The organizer is slower than the admin origin, as it is checked against the evenement.
+	freeze_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT, Default::default())
+	verify {
+		assert!(BevrorenTickets::<T>::contains_key(EVENEMENT, KLANT));
+	}
+
+	unfreeze_ticket {
+		let caller = organisator::<T>();
+		maak_evenement::<T>(&caller, 1_000);
+		koop_ticket::<T>(&caller, KLANT);
+		BevrorenTickets::<T>::insert(EVENEMENT, KLANT, T::Hash::default());
+	}: _(RawOrigin::Signed(caller), EVENEMENT, KLANT, Default::default())
+	verify {
+		assert!(!BevrorenTickets::<T>::contains_key(EVENEMENT, KLANT));
+	}
+}
+
+impl_benchmark_test_suite!(Upticket, crate::mock::new_test_ext(), crate::mock::Test);
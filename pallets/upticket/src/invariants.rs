@@ -0,0 +1,161 @@
+//! Property-based tests that drive random sequences of calls against the pallet and check the
+//! ticket supply and ownership invariants after every step.
+
+use crate::{mock::*, AantalTickets, EvenementId, KlantId, MaxPrijs, Prijs, Tickets};
+use proptest::prelude::*;
+use std::{collections::BTreeMap, convert::TryInto};
+
+#[derive(Clone, Debug)]
+enum Actie {
+	Maak { id: EvenementId, aantal_tickets: AantalTickets, max_prijs: MaxPrijs },
+	Koop { id: EvenementId, klant_id: KlantId, is_gescand: bool },
+	Verkoop { id: EvenementId, klant_oud: KlantId, klant_nieuw: KlantId, prijs: Prijs },
+	Scan { id: EvenementId, klant_id: KlantId },
+}
+
+// Small domains so that the random calls regularly hit the same evenementen and tickets.
+fn actie() -> impl Strategy<Value = Actie> {
+	prop_oneof![
+		(0..3u128, 0..4u128, 0..30u32).prop_map(|(id, aantal_tickets, max_prijs)| Actie::Maak {
+			id,
+			aantal_tickets,
+			max_prijs
+		}),
+		(0..3u128, 0..5u128, any::<bool>()).prop_map(|(id, klant_id, is_gescand)| Actie::Koop {
+			id,
+			klant_id,
+			is_gescand
+		}),
+		(0..3u128, 0..5u128, 0..5u128, 0..30u32).prop_map(|(id, klant_oud, klant_nieuw, prijs)| {
+			Actie::Verkoop { id, klant_oud, klant_nieuw, prijs }
+		}),
+		(0..3u128, 0..5u128).prop_map(|(id, klant_id)| Actie::Scan { id, klant_id }),
+	]
+}
+
+/// What the pallet is expected to hold after a sequence of calls.
+#[derive(Default)]
+struct Model {
+	evenementen: BTreeMap<EvenementId, (AantalTickets, MaxPrijs)>,
+	/// Whether the ticket of a klant has been scanned.
+	tickets: BTreeMap<(EvenementId, KlantId), bool>,
+}
+
+impl Model {
+	fn verkocht(&self, id: EvenementId) -> AantalTickets {
+		self.tickets.keys().filter(|(evenement_id, _)| *evenement_id == id).count() as u128
+	}
+
+	/// Applies `actie` and returns whether the pallet should accept it.
+	fn pas_toe(&mut self, actie: &Actie) -> bool {
+		match *actie {
+			Actie::Maak { id, aantal_tickets, max_prijs } => {
+				if id == 0 || self.evenementen.contains_key(&id) {
+					return false
+				}
+				self.evenementen.insert(id, (aantal_tickets, max_prijs));
+				true
+			},
+			Actie::Koop { id, klant_id, is_gescand } => {
+				let aantal_tickets = match self.evenementen.get(&id) {
+					Some((aantal_tickets, _)) => *aantal_tickets,
+					_ => return false,
+				};
+				if self.verkocht(id) >= aantal_tickets || self.tickets.contains_key(&(id, klant_id))
+				{
+					return false
+				}
+				self.tickets.insert((id, klant_id), is_gescand);
+				true
+			},
+			Actie::Verkoop { id, klant_oud, klant_nieuw, prijs } => {
+				let max_prijs = match self.evenementen.get(&id) {
+					Some((_, max_prijs)) => *max_prijs,
+					None => return false,
+				};
+				match self.tickets.get(&(id, klant_oud)) {
+					Some(false) => {},
+					_ => return false,
+				}
+				if max_prijs < prijs || self.tickets.contains_key(&(id, klant_nieuw)) {
+					return false
+				}
+				self.tickets.remove(&(id, klant_oud));
+				self.tickets.insert((id, klant_nieuw), false);
+				true
+			},
+			Actie::Scan { id, klant_id } => match self.tickets.get_mut(&(id, klant_id)) {
+				Some(is_gescand) if !*is_gescand => {
+					*is_gescand = true;
+					true
+				},
+				_ => false,
+			},
+		}
+	}
+}
+
+fn voer_uit(actie: &Actie) -> bool {
+	match *actie {
+		Actie::Maak { id, aantal_tickets, max_prijs } => UpticketModule::create_evenement(
+			Origin::signed(ALICE),
+			0,
+			max_prijs,
+			b"Concert".to_vec().try_into().unwrap(),
+			id,
+			aantal_tickets,
+			0,
+			0,
+		),
+		Actie::Koop { id, klant_id, is_gescand } => {
+			UpticketModule::buy_ticket(Origin::signed(BOB), id, klant_id, is_gescand, None, None)
+		},
+		Actie::Verkoop { id, klant_oud, klant_nieuw, prijs } => {
+			UpticketModule::sell_ticket(Origin::signed(BOB), id, klant_oud, klant_nieuw, prijs)
+		},
+		Actie::Scan { id, klant_id } => {
+			UpticketModule::scan_ticket(Origin::signed(CHARLIE), id, klant_id)
+		},
+	}
+	.is_ok()
+}
+
+fn controleer(model: &Model) {
+	assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+
+	for (&id, &(aantal_tickets, _)) in &model.evenementen {
+		let tickets: BTreeMap<_, _> = Tickets::<Test>::iter_prefix(id)
+			.map(|(klant_id, ticket)| ((id, klant_id), ticket.is_gescand))
+			.collect();
+		let verwacht: BTreeMap<_, _> = model
+			.tickets
+			.iter()
+			.filter(|((evenement_id, _), _)| *evenement_id == id)
+			.map(|(sleutel, is_gescand)| (*sleutel, *is_gescand))
+			.collect();
+
+		// Every ticket has exactly one owner, and scanned tickets stay with that owner.
+		assert_eq!(tickets, verwacht);
+		// Tickets minted never exceed the supply, so availability cannot underflow.
+		assert!(tickets.len() as u128 <= aantal_tickets);
+		assert_eq!(UpticketModule::verkocht(id), tickets.len() as u128);
+		assert_eq!(UpticketModule::get_beschikbaarheid(id), aantal_tickets - tickets.len() as u128);
+		// Tickets bought as scanned count as attendees as well.
+		let gescand = tickets.values().filter(|is_gescand| **is_gescand).count();
+		assert_eq!(UpticketModule::aanwezigen(id), gescand as u128);
+	}
+}
+
+proptest! {
+	#[test]
+	fn ticket_invarianten_blijven_gelden(acties in prop::collection::vec(actie(), 1..64)) {
+		new_test_ext().execute_with(|| {
+			let mut model = Model::default();
+			for actie in &acties {
+				let verwacht = model.pas_toe(actie);
+				assert_eq!(voer_uit(actie), verwacht, "{:?}", actie);
+				controleer(&model);
+			}
+		});
+	}
+}
@@ -2,73 +2,514 @@
 
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod invariants;
+pub mod migrations;
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		sp_runtime::{
+			traits::{Hash, SaturatedConversion, Saturating, Zero},
+			DispatchError, Perbill, Permill,
+		},
+		traits::{BalanceStatus, Currency, ReservableCurrency, StorageVersion, UnixTime},
+		transactional,
+	};
 	use frame_system::pallet_prelude::*;
 	use scale_info::TypeInfo;
-	use sp_std::vec::Vec;
+	use sp_std::{convert::TryInto, vec::Vec};
+
+	use crate::WeightInfo;
 
 	pub type EvenementId = u128;
 	pub type Prijs = u32;
 	pub type MaxPrijs = u32;
-	pub type Naam = Vec<u8>;
+	pub type Naam<T> = BoundedVec<u8, <T as Config>::MaxNaamLengte>;
 	pub type KlantId = u128;
+	pub type Code<T> = BoundedVec<u8, <T as Config>::MaxCodeLengte>;
+	pub type Bewijs<T> =
+		BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxBewijsLengte>;
 	pub type AantalTickets = u128;
+	/// Milliseconds since the unix epoch.
+	pub type Tijdstip = u64;
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// A clock that benchmarks can set, as [`UnixTime`] cannot be set.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkTijd {
+		/// Makes `Tijd` return `millis` from now on.
+		fn zet(millis: Tijdstip);
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Source of the current time, used to timestamp ticket scans.
+		type Tijd: UnixTime;
+
+		/// Sets `Tijd` in benchmarks, so they can reach calls that wait for an evenement to end.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkTijd: BenchmarkTijd;
+
+		/// The maximum number of scans kept in the log of a single ticket. When the log is full
+		/// the oldest scan is dropped, and with 0 no scans are logged.
+		#[pallet::constant]
+		type MaxScanRegistraties: Get<u32>;
+
+		/// The maximum length of the name of an evenement.
+		#[pallet::constant]
+		type MaxNaamLengte: Get<u32>;
+
+		/// The maximum length of a single metadata field of an evenement.
+		#[pallet::constant]
+		type MaxTekstLengte: Get<u32>;
+
+		/// The maximum number of artists listed in the metadata of an evenement.
+		#[pallet::constant]
+		type MaxArtiesten: Get<u32>;
+
+		/// The maximum length of a voucher code.
+		#[pallet::constant]
+		type MaxCodeLengte: Get<u32>;
+
+		/// The maximum number of hashes in a Merkle proof of presale access, which is the depth
+		/// of the largest allowlist that can be used.
+		#[pallet::constant]
+		type MaxBewijsLengte: Get<u32>;
+
+		/// The currency in which storage deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The base deposit reserved from the organizer for creating an evenement.
+		#[pallet::constant]
+		type EvenementBorg: Get<BalanceOf<Self>>;
+
+		/// The additional deposit reserved per byte of the name and metadata of an evenement.
+		#[pallet::constant]
+		type BorgPerByte: Get<BalanceOf<Self>>;
+
+		/// The additional deposit reserved per ticket an evenement can issue.
+		#[pallet::constant]
+		type TicketBorg: Get<BalanceOf<Self>>;
+
+		/// How long after its end an evenement can be purged, in milliseconds.
+		#[pallet::constant]
+		type OpruimTermijn: Get<Tijdstip>;
+
+		/// The share of the deposit of an evenement paid to whoever purges it.
+		#[pallet::constant]
+		type OpruimBeloning: Get<Perbill>;
+
+		/// The maximum number of tickets, scan logs, freezes, names, presale purchases,
+		/// redemptions and vouchers together removed by a single call to `purge_evenement` or
+		/// `force_cancel_evenement`.
+		#[pallet::constant]
+		type MaxOpruimPerAanroep: Get<u32>;
+
+		/// The origin that administers the pallet, such as banning organizers and resolving
+		/// disputes about tickets.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Whether and for how much tickets of an evenement can change klant.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TransferBeleid {
+		/// Tickets stay with the klant that bought them.
+		Onoverdraagbaar,
+		/// Tickets can be resold for at most the maximum price of the evenement.
+		BegrensdeDoorverkoop,
+		/// Tickets can be resold for any price.
+		VrijeMarkt,
+	}
+
+	impl Default for TransferBeleid {
+		fn default() -> Self {
+			Self::BegrensdeDoorverkoop
+		}
 	}
 
-	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, Default)]
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
 	#[scale_info(skip_type_params(T))]
-	pub struct Evenement<EvenementId, Naam, Prijs, MaxPrijs, AantalTickets> {
-		id: EvenementId,
-		naam: Naam,
-		prijs: Prijs,
-		max_prijs: MaxPrijs,
-		aantal_tickets: AantalTickets,
+	pub struct Evenement<AccountId, EvenementId, Naam, Prijs, MaxPrijs, AantalTickets> {
+		pub id: EvenementId,
+		pub naam: Naam,
+		pub prijs: Prijs,
+		pub max_prijs: MaxPrijs,
+		pub aantal_tickets: AantalTickets,
+		pub organisator: AccountId,
+		pub begin: Tijdstip,
+		pub einde: Tijdstip,
+		pub transfer_beleid: TransferBeleid,
 	}
 
+	pub type EvenementOf<T> = Evenement<
+		<T as frame_system::Config>::AccountId,
+		EvenementId,
+		Naam<T>,
+		Prijs,
+		MaxPrijs,
+		AantalTickets,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn evenementen)]
-	pub(super) type Evenementen<T: Config> = StorageMap<
+	pub(super) type Evenementen<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, EvenementOf<T>, ValueQuery>;
+
+	/// Descriptive information about an evenement, kept apart from its pricing so organizers can
+	/// update it on its own.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+	pub struct EvenementMetadata<Tekst, Artiesten> {
+		pub locatie: Tekst,
+		pub beschrijving: Tekst,
+		/// Content hash or CID of the poster.
+		pub poster: Tekst,
+		/// Content hash or CID of the terms and conditions.
+		pub voorwaarden: Tekst,
+		pub artiesten: Artiesten,
+		/// Minimum age of visitors, if any.
+		pub minimum_leeftijd: Option<u8>,
+	}
+
+	pub type Tekst<T> = BoundedVec<u8, <T as Config>::MaxTekstLengte>;
+
+	pub type EvenementMetadataOf<T> =
+		EvenementMetadata<Tekst<T>, BoundedVec<Tekst<T>, <T as Config>::MaxArtiesten>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn metadata)]
+	pub(super) type Metadata<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, EvenementMetadataOf<T>, OptionQuery>;
+
+	/// The rules an organizer sets for passing tickets of an evenement on to another klant, by
+	/// gift or by sale, if its [`TransferBeleid`] allows that at all. Without rules tickets can be
+	/// passed on until the evenement ends.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Overdrachtsregels {
+		/// Tickets can no longer be passed on this many hours before the evenement begins.
+		pub sluiting_uren: Option<u32>,
+		/// Whether a ticket can only be passed on to a klant with the name on the ticket.
+		pub naam_verplicht: bool,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn overdrachtsregels)]
+	pub(super) type Regels<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, Overdrachtsregels, OptionQuery>;
+
+	/// The hash of the name of the holder of a ticket, as set by the organizer.
+	#[pallet::storage]
+	#[pallet::getter(fn ticket_namen)]
+	pub(super) type TicketNamen<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EvenementId,
+		Blake2_128Concat,
+		KlantId,
+		T::Hash,
+		OptionQuery,
+	>;
+
+	/// The discount a voucher gives on the price of a ticket.
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Korting {
+		/// A share of the price.
+		Percentage(Permill),
+		/// A fixed amount off the price.
+		Bedrag(Prijs),
+	}
+
+	/// A voucher code registered by the organizer of an evenement.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Voucher {
+		pub korting: Korting,
+		/// How many tickets can be bought with the code.
+		pub max_gebruik: u32,
+		/// The code can no longer be redeemed from this moment on.
+		pub verloopt: Tijdstip,
+	}
+
+	/// Vouchers per evenement, keyed by the hash of their code so codes cannot be read from
+	/// storage.
+	#[pallet::storage]
+	#[pallet::getter(fn vouchers)]
+	pub(super) type Vouchers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EvenementId,
+		Blake2_128Concat,
+		T::Hash,
+		Voucher,
+		OptionQuery,
+	>;
+
+	/// Number of tickets bought with a voucher.
+	#[pallet::storage]
+	#[pallet::getter(fn inwisselingen)]
+	pub(super) type Inwisselingen<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EvenementId,
+		Blake2_128Concat,
+		T::Hash,
+		u32,
+		ValueQuery,
+	>;
+
+	/// A presale of an evenement, during which only accounts on an allowlist can buy tickets.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Voorverkoop<Hash> {
+		/// The Merkle root of the allowlist. Leaves are the hashes of the encoded accounts and
+		/// every pair of nodes is sorted before it is hashed.
+		pub merkle_root: Hash,
+		/// The moment the public sale starts and the presale ends.
+		pub einde: Tijdstip,
+		/// The number of tickets every account on the allowlist can buy during the presale.
+		pub max_per_lid: u32,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn voorverkopen)]
+	pub(super) type Voorverkopen<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, Voorverkoop<T::Hash>, OptionQuery>;
+
+	/// The number of tickets every account bought during the presale of an evenement.
+	#[pallet::storage]
+	#[pallet::getter(fn voorverkoop_aankopen)]
+	pub(super) type VoorverkoopAankopen<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		EvenementId,
-		Evenement<EvenementId, Naam, Prijs, MaxPrijs, AantalTickets>,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
 		ValueQuery,
 	>;
 
-	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, Default)]
+	/// The deposit currently reserved from the organizer of an evenement.
+	#[pallet::storage]
+	#[pallet::getter(fn borgen)]
+	pub(super) type Borgen<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, BalanceOf<T>, ValueQuery>;
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Ticket<Bool> {
-		is_gescand: Bool,
+		pub is_gescand: Bool,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn tickets)]
-	pub(super) type Tickets<T: Config> = StorageMap<
+	pub(super) type Tickets<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		(EvenementId, KlantId),
+		EvenementId,
+		Blake2_128Concat,
+		KlantId,
 		Ticket<bool>,
 		ValueQuery,
 	>;
 
+	/// Number of sold tickets per evenement.
+	#[pallet::storage]
+	#[pallet::getter(fn verkocht)]
+	pub(super) type Verkocht<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, AantalTickets, ValueQuery>;
+
+	/// A single successful scan of a ticket at the gate.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ScanRegistratie<AccountId, BlockNumber> {
+		pub blok: BlockNumber,
+		pub tijdstip: Tijdstip,
+		pub scanner: AccountId,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn scan_registraties)]
+	pub(super) type ScanRegistraties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EvenementId,
+		Blake2_128Concat,
+		KlantId,
+		BoundedVec<ScanRegistratie<T::AccountId, T::BlockNumber>, T::MaxScanRegistraties>,
+		ValueQuery,
+	>;
+
+	/// Number of scanned tickets per evenement.
+	#[pallet::storage]
+	#[pallet::getter(fn aanwezigen)]
+	pub(super) type Aanwezigen<T: Config> =
+		StorageMap<_, Blake2_128Concat, EvenementId, u128, ValueQuery>;
+
+	/// Tickets that cannot be scanned or sold, with the hash of the reason they were frozen.
+	#[pallet::storage]
+	#[pallet::getter(fn bevroren_tickets)]
+	pub(super) type BevrorenTickets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		EvenementId,
+		Blake2_128Concat,
+		KlantId,
+		T::Hash,
+		OptionQuery,
+	>;
+
+	/// Organizers that may no longer create evenementen.
+	#[pallet::storage]
+	#[pallet::getter(fn gebande_organisatoren)]
+	pub(super) type GebandeOrganisatoren<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Evenementen as `(id, organisator, naam, prijs, max_prijs, aantal_tickets, begin, einde)`.
+		/// The deposit is reserved from the organisator.
+		pub evenementen: Vec<(
+			EvenementId,
+			T::AccountId,
+			Vec<u8>,
+			Prijs,
+			MaxPrijs,
+			AantalTickets,
+			Tijdstip,
+			Tijdstip,
+		)>,
+		/// Pre-issued tickets as `(evenement_id, klant_id, is_gescand)`.
+		pub tickets: Vec<(EvenementId, KlantId, bool)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { evenementen: Default::default(), tickets: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (id, organisator, naam, prijs, max_prijs, aantal_tickets, begin, einde) in
+				&self.evenementen
+			{
+				assert!(*id != 0, "Evenement id 0 is reserved");
+				assert!(!<Evenementen<T>>::contains_key(id), "Duplicate evenement in genesis");
+
+				let evenement = Evenement {
+					id: *id,
+					naam: naam.clone().try_into().expect("Evenement naam is too long"),
+					prijs: *prijs,
+					max_prijs: *max_prijs,
+					aantal_tickets: *aantal_tickets,
+					organisator: organisator.clone(),
+					begin: *begin,
+					einde: *einde,
+					transfer_beleid: Default::default(),
+				};
+				Pallet::<T>::pas_borg_aan(&evenement, Pallet::<T>::borg_voor(&evenement, None))
+					.expect("Organisator cannot pay the evenement deposit");
+				<Evenementen<T>>::insert(id, evenement);
+			}
+
+			for (evenement_id, klant_id, is_gescand) in &self.tickets {
+				assert!(
+					<Evenementen<T>>::contains_key(evenement_id),
+					"Ticket for an unknown evenement in genesis"
+				);
+				assert!(
+					!<Tickets<T>>::contains_key(evenement_id, klant_id),
+					"Duplicate ticket in genesis"
+				);
+				assert!(
+					Pallet::<T>::get_beschikbaarheid(*evenement_id) > 0,
+					"More genesis tickets than the evenement has"
+				);
+
+				<Verkocht<T>>::mutate(evenement_id, |verkocht| *verkocht += 1);
+				if *is_gescand {
+					<Aanwezigen<T>>::mutate(evenement_id, |aanwezigen| *aanwezigen += 1);
+				}
+				<Tickets<T>>::insert(evenement_id, klant_id, Ticket { is_gescand: *is_gescand });
+			}
+		}
+	}
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		EvenementAangemaakt(Evenement<EvenementId, Naam, Prijs, MaxPrijs, AantalTickets>),
-		TicketAangemaakt(Ticket<bool>),
-		Evenement(Evenement<EvenementId, Naam, Prijs, MaxPrijs, AantalTickets>),
-		Ticket(Ticket<bool>),
+		EvenementAangemaakt(EvenementOf<T>),
+		/// A ticket was bought. \[evenement_id, klant_id, ticket\]
+		TicketAangemaakt(EvenementId, KlantId, Ticket<bool>),
+		Evenement(EvenementOf<T>),
+		/// A ticket was resold. \[evenement_id, klant_oud, klant_nieuw, prijs\]
+		TicketVerkocht(EvenementId, KlantId, KlantId, Prijs),
 		Beschikbaarheid(u128),
+		/// A ticket was scanned. \[evenement_id, klant_id, scanner\]
+		TicketGescand(EvenementId, KlantId, T::AccountId),
+		/// The metadata of an evenement was updated. \[evenement_id\]
+		EvenementMetadataGewijzigd(EvenementId),
+		/// An evenement without sold tickets was removed by its organizer. \[evenement_id\]
+		EvenementVerwijderd(EvenementId),
+		/// An ended evenement and all of its tickets were purged.
+		/// \[evenement_id, verkocht, gescand, opruimer, beloning\]
+		EvenementOpgeruimd(EvenementId, AantalTickets, u128, T::AccountId, BalanceOf<T>),
+		/// An organizer was banned from creating evenementen. \[organisator\]
+		OrganisatorGebannen(T::AccountId),
+		/// A banned organizer was allowed to create evenementen again. \[organisator\]
+		OrganisatorToegelaten(T::AccountId),
+		/// A ticket was moved to another klant by the admin origin.
+		/// \[evenement_id, klant_oud, klant_nieuw, reden\]
+		TicketOvergedragen(EvenementId, KlantId, KlantId, T::Hash),
+		/// The scan of a ticket was undone by the admin origin. \[evenement_id, klant_id, reden\]
+		ScanIngetrokken(EvenementId, KlantId, T::Hash),
+		/// An evenement and all of its tickets were removed by the admin origin.
+		/// \[evenement_id, verkocht, reden\]
+		EvenementGeannuleerd(EvenementId, AantalTickets, T::Hash),
+		/// A ticket was frozen. \[evenement_id, klant_id, reden\]
+		TicketBevroren(EvenementId, KlantId, T::Hash),
+		/// The freeze of a ticket was lifted. \[evenement_id, klant_id, reden\]
+		TicketVrijgegeven(EvenementId, KlantId, T::Hash),
+		/// A ticket was given away. \[evenement_id, klant_oud, klant_nieuw\]
+		TicketGeschonken(EvenementId, KlantId, KlantId),
+		/// The transfer policy of an evenement was changed. \[evenement_id, beleid\]
+		TransferBeleidGewijzigd(EvenementId, TransferBeleid),
+		/// The transfer rules of an evenement were changed. \[evenement_id\]
+		OverdrachtsregelsGewijzigd(EvenementId),
+		/// The name on a ticket was changed. \[evenement_id, klant_id\]
+		TicketNaamGewijzigd(EvenementId, KlantId),
+		/// A voucher was registered. \[evenement_id, code_hash\]
+		VoucherToegevoegd(EvenementId, T::Hash),
+		/// A voucher was removed. \[evenement_id, code_hash\]
+		VoucherVerwijderd(EvenementId, T::Hash),
+		/// A ticket was bought with a voucher. \[evenement_id, klant_id, code_hash, prijs\]
+		VoucherIngewisseld(EvenementId, KlantId, T::Hash, Prijs),
+		/// The presale of an evenement was changed. \[evenement_id\]
+		VoorverkoopGewijzigd(EvenementId),
 	}
 
 	#[pallet::error]
@@ -80,30 +521,160 @@ pub mod pallet {
 		MaxPriceExceeded,
 		NoTicketsAvailable,
 		TicketAlreadyScanned,
+		EvenementAlreadyExists,
+		NotOrganizer,
+		TicketsAlreadySold,
+		EvenementNotEnded,
+		TicketAlreadyOwned,
+		OrganizerBanned,
+		OrganizerNotBanned,
+		TicketNotScanned,
+		TicketFrozen,
+		TicketAlreadyFrozen,
+		TicketNotFrozen,
+		TransferDeadlinePassed,
+		NameMismatch,
+		TicketNotTransferable,
+		VoucherAlreadyExists,
+		VoucherNotFound,
+		VoucherExpired,
+		VoucherExhausted,
+		VouchersRemaining,
+		NotOnAllowlist,
+		PresaleLimitReached,
+		InvalidEvenementId,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::create_evenement(naam.len() as u32))]
 		pub fn create_evenement(
 			origin: OriginFor<T>,
 			prijs: Prijs,
 			max_prijs: MaxPrijs,
-			naam: Naam,
+			naam: Naam<T>,
 			id: EvenementId,
 			aantal_tickets: AantalTickets,
+			begin: Tijdstip,
+			einde: Tijdstip,
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			let organisator = ensure_signed(origin)?;
 
-			let evenement = Evenement { id, prijs, max_prijs, naam, aantal_tickets };
+			// Id 0 marks a missing evenement, so such an evenement could never be removed.
+			ensure!(id != 0, Error::<T>::InvalidEvenementId);
+
+			if <GebandeOrganisatoren<T>>::contains_key(&organisator) {
+				Err(Error::<T>::OrganizerBanned)?
+			}
+
+			if <Evenementen<T>>::contains_key(id) {
+				Err(Error::<T>::EvenementAlreadyExists)?
+			}
 
+			let evenement = Evenement {
+				id,
+				prijs,
+				max_prijs,
+				naam,
+				aantal_tickets,
+				organisator,
+				begin,
+				einde,
+				transfer_beleid: Default::default(),
+			};
+
+			Self::pas_borg_aan(&evenement, Self::borg_voor(&evenement, None))?;
 			<Evenementen<T>>::insert(id, evenement.clone());
 
 			Self::deposit_event(Event::EvenementAangemaakt(evenement));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::set_evenement_metadata(
+			T::MaxTekstLengte::get(),
+			metadata.artiesten.len() as u32,
+		))]
+		pub fn set_evenement_metadata(
+			origin: OriginFor<T>,
+			id: EvenementId,
+			metadata: EvenementMetadataOf<T>,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			Self::pas_borg_aan(&evenement, Self::borg_voor(&evenement, Some(&metadata)))?;
+			<Metadata<T>>::insert(id, metadata);
+
+			Self::deposit_event(Event::EvenementMetadataGewijzigd(id));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_evenement())]
+		pub fn remove_evenement(origin: OriginFor<T>, id: EvenementId) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			if <Verkocht<T>>::get(id) != 0 {
+				Err(Error::<T>::TicketsAlreadySold)?
+			}
+
+			// Vouchers left behind would apply to a new evenement with the same id.
+			if <Vouchers<T>>::iter_prefix(id).next().is_some() {
+				Err(Error::<T>::VouchersRemaining)?
+			}
+
+			T::Currency::unreserve(&evenement.organisator, <Borgen<T>>::take(id));
+			<Evenementen<T>>::remove(id);
+			<Metadata<T>>::remove(id);
+			<Regels<T>>::remove(id);
+			<Voorverkopen<T>>::remove(id);
+
+			Self::deposit_event(Event::EvenementVerwijderd(id));
+			Ok(())
+		}
+
+		/// Removes an evenement once it has ended and the purge period has passed. Tickets are
+		/// removed in chunks, so the call has to be repeated until `EvenementOpgeruimd` is
+		/// emitted. The caller is rewarded with a share of the deposit of the organizer. Nothing is
+		/// removed if the reward cannot be paid.
+		#[pallet::weight(T::WeightInfo::purge_evenement(T::MaxOpruimPerAanroep::get()))]
+		#[transactional]
+		pub fn purge_evenement(origin: OriginFor<T>, id: EvenementId) -> DispatchResult {
+			let opruimer = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			let nu = T::Tijd::now().as_millis() as Tijdstip;
+			if nu < evenement.einde.saturating_add(T::OpruimTermijn::get()) {
+				Err(Error::<T>::EvenementNotEnded)?
+			}
+
+			Self::ruim_op(evenement, opruimer)
+		}
+
+		#[pallet::weight(T::WeightInfo::get_evenement())]
 		pub fn get_evenement(origin: OriginFor<T>, id: EvenementId) -> DispatchResult {
 			ensure_signed(origin)?;
 
@@ -117,7 +688,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::get_evenement_beschikbaarheid())]
 		pub fn get_evenement_beschikbaarheid(origin: OriginFor<T>, id: EvenementId) -> DispatchResult {
 			ensure_signed(origin)?;
 
@@ -132,14 +703,19 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		/// Buys a ticket for `klant_id`. With a voucher `code` the discounted price is emitted in
+		/// `VoucherIngewisseld`. During a presale `bewijs` has to prove that the buyer is on the
+		/// allowlist, and every account on it can buy up to `max_per_lid` tickets.
+		#[pallet::weight(T::WeightInfo::buy_ticket(bewijs.as_ref().map_or(0, |b| b.len() as u32)))]
 		pub fn buy_ticket(
 			origin: OriginFor<T>,
 			evenement_id: EvenementId,
 			klant_id: KlantId,
 			is_gescand: bool,
+			code: Option<Code<T>>,
+			bewijs: Option<Bewijs<T>>,
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			let koper = ensure_signed(origin)?;
 
 			let evenement = <Evenementen<T>>::get(evenement_id);
 
@@ -147,47 +723,112 @@ pub mod pallet {
 				Err(Error::<T>::EvenementNotFound)?
 			}
 
+			let mut in_voorverkoop = false;
+			if let Some(voorverkoop) = <Voorverkopen<T>>::get(evenement_id) {
+				let nu = T::Tijd::now().as_millis() as Tijdstip;
+				if nu < voorverkoop.einde {
+					if !Self::op_toegangslijst(&voorverkoop.merkle_root, &koper, bewijs) {
+						Err(Error::<T>::NotOnAllowlist)?
+					}
+					// Tickets are not bound to accounts, so every purchase of a member counts.
+					let aankopen = <VoorverkoopAankopen<T>>::get(evenement_id, &koper);
+					if aankopen >= voorverkoop.max_per_lid {
+						Err(Error::<T>::PresaleLimitReached)?
+					}
+					in_voorverkoop = true;
+				}
+			}
+
 			let beschikbaarheid = Self::get_beschikbaarheid(evenement.id);
 
 			if beschikbaarheid < 1 {
 				Err(Error::<T>::NoTicketsAvailable)?
 			}
 
+			// Overwriting an owned ticket would reset its scan status.
+			if <Tickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketAlreadyOwned)?
+			}
+
+			let inwisseling = match code {
+				Some(code) => Some(Self::controleer_voucher(&evenement, &code)?),
+				None => None,
+			};
+
 			let ticket = Ticket { is_gescand };
 
-			<Tickets<T>>::insert((evenement_id, klant_id), ticket.clone());
+			<Verkocht<T>>::mutate(evenement_id, |verkocht| *verkocht += 1);
+			// A ticket handed out at the gate counts as attending right away.
+			if is_gescand {
+				<Aanwezigen<T>>::mutate(evenement_id, |aanwezigen| *aanwezigen += 1);
+			}
+			<Tickets<T>>::insert(evenement_id, klant_id, ticket.clone());
+			if in_voorverkoop {
+				<VoorverkoopAankopen<T>>::mutate(evenement_id, &koper, |aankopen| *aankopen += 1);
+			}
+
+			Self::deposit_event(Event::TicketAangemaakt(evenement_id, klant_id, ticket));
 
-			Self::deposit_event(Event::TicketAangemaakt(ticket));
+			if let Some((code_hash, prijs)) = inwisseling {
+				<Inwisselingen<T>>::mutate(evenement_id, code_hash, |gebruikt| *gebruikt += 1);
+				Self::deposit_event(Event::VoucherIngewisseld(
+					evenement_id,
+					klant_id,
+					code_hash,
+					prijs,
+				));
+			}
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::scan_ticket())]
 		pub fn scan_ticket(
 			origin: OriginFor<T>,
 			evenement_id: EvenementId,
 			klant_id: KlantId,
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			let scanner = ensure_signed(origin)?;
 
-			if <Tickets<T>>::contains_key((evenement_id, klant_id)) == false {
+			if !<Tickets<T>>::contains_key(evenement_id, klant_id) {
 				Err(Error::<T>::TicketNotFound)?
 			}
 
-			let mut ticket = <Tickets<T>>::get((evenement_id, klant_id));
+			if <BevrorenTickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketFrozen)?
+			}
+
+			let mut ticket = <Tickets<T>>::get(evenement_id, klant_id);
 
-			if ticket.is_gescand == true {
+			if ticket.is_gescand {
 				Err(Error::<T>::TicketAlreadyScanned)?
 			}
 
 			ticket.is_gescand = true;
 
-			<Tickets<T>>::insert((evenement_id, klant_id), &ticket);
+			<Tickets<T>>::insert(evenement_id, klant_id, &ticket);
 
-			Self::deposit_event(Event::Ticket(ticket));
+			let registratie = ScanRegistratie {
+				blok: <frame_system::Pallet<T>>::block_number(),
+				tijdstip: T::Tijd::now().as_millis() as Tijdstip,
+				scanner: scanner.clone(),
+			};
+			// Without room for a single scan no log is kept.
+			if T::MaxScanRegistraties::get() > 0 {
+				<ScanRegistraties<T>>::mutate(evenement_id, klant_id, |registraties| {
+					if registraties.len() >= T::MaxScanRegistraties::get() as usize {
+						registraties.remove(0);
+					}
+					// Cannot fail: room was made above.
+					let _ = registraties.try_push(registratie);
+				});
+			}
+			<Aanwezigen<T>>::mutate(evenement_id, |aanwezigen| *aanwezigen += 1);
+
+			Self::deposit_event(Event::TicketGescand(evenement_id, klant_id, scanner));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::sell_ticket())]
 		pub fn sell_ticket(
 			origin: OriginFor<T>,
 			evenement_id: EvenementId,
@@ -197,50 +838,753 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_signed(origin)?;
 
-			if <Evenementen<T>>::contains_key(evenement_id) == false {
+			if !<Evenementen<T>>::contains_key(evenement_id) {
 				Err(Error::<T>::EvenementNotFound)?
 			}
 
-			if <Tickets<T>>::contains_key((evenement_id, klant_oud)) == false {
+			if !<Tickets<T>>::contains_key(evenement_id, klant_oud) {
 				Err(Error::<T>::TicketNotFound)?
 			}
 
-			let ticket = <Tickets<T>>::get((evenement_id, klant_oud));
+			if <BevrorenTickets<T>>::contains_key(evenement_id, klant_oud) {
+				Err(Error::<T>::TicketFrozen)?
+			}
+
+			let ticket = <Tickets<T>>::get(evenement_id, klant_oud);
 
-			if ticket.is_gescand == true {
+			if ticket.is_gescand {
 				Err(Error::<T>::TicketAlreadyScanned)?
 			}
 
 			let evenement = <Evenementen<T>>::get(evenement_id);
 
-			if evenement.max_prijs < prijs {
+			// A sale cannot prove the name of the buyer, so it fails if names must match.
+			Self::controleer_overdracht(&evenement, klant_oud, None)?;
+
+			if evenement.transfer_beleid == TransferBeleid::BegrensdeDoorverkoop &&
+				evenement.max_prijs < prijs
+			{
 				Err(Error::<T>::MaxPriceExceeded)?
 			}
 
-			<Tickets<T>>::swap(
-				(evenement_id, klant_oud), 
-				(evenement_id, klant_nieuw)
-			);
+			// A swap would hand the seller the buyer's existing ticket.
+			if <Tickets<T>>::contains_key(evenement_id, klant_nieuw) {
+				Err(Error::<T>::TicketAlreadyOwned)?
+			}
+
+			<Tickets<T>>::swap(evenement_id, klant_oud, evenement_id, klant_nieuw);
+			<TicketNamen<T>>::remove(evenement_id, klant_oud);
 
-			let ticket = <Tickets<T>>::get((evenement_id, klant_nieuw));
-			Self::deposit_event(Event::Ticket(ticket));
+			Self::deposit_event(Event::TicketVerkocht(evenement_id, klant_oud, klant_nieuw, prijs));
+			Ok(())
+		}
+
+		/// Gives the ticket of `klant_oud` to `klant_nieuw` for free, within the transfer rules of
+		/// the evenement. `naam` is the hash of the name of `klant_nieuw`, which the ticket keeps if
+		/// it matches the name on the ticket.
+		#[pallet::weight(T::WeightInfo::transfer_ticket())]
+		pub fn transfer_ticket(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_oud: KlantId,
+			klant_nieuw: KlantId,
+			naam: Option<T::Hash>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(evenement_id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if !<Tickets<T>>::contains_key(evenement_id, klant_oud) {
+				Err(Error::<T>::TicketNotFound)?
+			}
+
+			if <BevrorenTickets<T>>::contains_key(evenement_id, klant_oud) {
+				Err(Error::<T>::TicketFrozen)?
+			}
+
+			if <Tickets<T>>::get(evenement_id, klant_oud).is_gescand {
+				Err(Error::<T>::TicketAlreadyScanned)?
+			}
+
+			Self::controleer_overdracht(&evenement, klant_oud, naam)?;
+
+			if <Tickets<T>>::contains_key(evenement_id, klant_nieuw) {
+				Err(Error::<T>::TicketAlreadyOwned)?
+			}
+
+			<Tickets<T>>::swap(evenement_id, klant_oud, evenement_id, klant_nieuw);
+			let naam_oud = <TicketNamen<T>>::take(evenement_id, klant_oud);
+			if let Some(naam) = naam.filter(|naam| naam_oud.as_ref() == Some(naam)) {
+				<TicketNamen<T>>::insert(evenement_id, klant_nieuw, naam);
+			}
+
+			Self::deposit_event(Event::TicketGeschonken(evenement_id, klant_oud, klant_nieuw));
+			Ok(())
+		}
+
+		/// Sets whether and for how much tickets of an evenement can be resold. Tickets of
+		/// evenementen that are not transferable cannot be given away either.
+		#[pallet::weight(T::WeightInfo::set_transfer_beleid())]
+		pub fn set_transfer_beleid(
+			origin: OriginFor<T>,
+			id: EvenementId,
+			beleid: TransferBeleid,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let mut evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			evenement.transfer_beleid = beleid;
+			<Evenementen<T>>::insert(id, evenement);
+
+			Self::deposit_event(Event::TransferBeleidGewijzigd(id, beleid));
+			Ok(())
+		}
+
+		/// Sets the rules for passing tickets of an evenement on, or removes them with `None` so
+		/// tickets can be passed on freely.
+		#[pallet::weight(T::WeightInfo::set_overdrachtsregels())]
+		pub fn set_overdrachtsregels(
+			origin: OriginFor<T>,
+			id: EvenementId,
+			regels: Option<Overdrachtsregels>,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			match regels {
+				Some(regels) => <Regels<T>>::insert(id, regels),
+				None => <Regels<T>>::remove(id),
+			}
+
+			Self::deposit_event(Event::OverdrachtsregelsGewijzigd(id));
+			Ok(())
+		}
+
+		/// Puts the hash of the name of its holder on a ticket, or removes it with `None`.
+		#[pallet::weight(T::WeightInfo::set_ticket_naam())]
+		pub fn set_ticket_naam(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_id: KlantId,
+			naam: Option<T::Hash>,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(evenement_id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			if !<Tickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketNotFound)?
+			}
+
+			match naam {
+				Some(naam) => <TicketNamen<T>>::insert(evenement_id, klant_id, naam),
+				None => <TicketNamen<T>>::remove(evenement_id, klant_id),
+			}
+
+			Self::deposit_event(Event::TicketNaamGewijzigd(evenement_id, klant_id));
+			Ok(())
+		}
+
+		/// Starts a presale of an evenement, during which only accounts on the allowlist with
+		/// Merkle root `merkle_root` can buy tickets, or ends it with `None`. Purchases are counted
+		/// per evenement, so replacing the presale does not reset the limit of its members.
+		#[pallet::weight(T::WeightInfo::set_voorverkoop())]
+		pub fn set_voorverkoop(
+			origin: OriginFor<T>,
+			id: EvenementId,
+			voorverkoop: Option<Voorverkoop<T::Hash>>,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			match voorverkoop {
+				Some(voorverkoop) => <Voorverkopen<T>>::insert(id, voorverkoop),
+				None => <Voorverkopen<T>>::remove(id),
+			}
+
+			Self::deposit_event(Event::VoorverkoopGewijzigd(id));
+			Ok(())
+		}
+
+		/// Registers a voucher for an evenement. `code_hash` is the hash of the evenement id and
+		/// the code, see `code_hash`. The code is handed out off-chain and given to `buy_ticket`
+		/// by buyers.
+		#[pallet::weight(T::WeightInfo::add_voucher())]
+		pub fn add_voucher(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			code_hash: T::Hash,
+			voucher: Voucher,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(evenement_id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			if <Vouchers<T>>::contains_key(evenement_id, code_hash) {
+				Err(Error::<T>::VoucherAlreadyExists)?
+			}
+
+			<Vouchers<T>>::insert(evenement_id, code_hash, voucher);
+
+			Self::deposit_event(Event::VoucherToegevoegd(evenement_id, code_hash));
+			Ok(())
+		}
+
+		/// Removes a voucher of an evenement together with its number of redemptions.
+		#[pallet::weight(T::WeightInfo::remove_voucher())]
+		pub fn remove_voucher(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			code_hash: T::Hash,
+		) -> DispatchResult {
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(evenement_id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			if <Vouchers<T>>::take(evenement_id, code_hash).is_none() {
+				Err(Error::<T>::VoucherNotFound)?
+			}
+			<Inwisselingen<T>>::remove(evenement_id, code_hash);
+
+			Self::deposit_event(Event::VoucherVerwijderd(evenement_id, code_hash));
+			Ok(())
+		}
+
+		/// Bans `organisator` from creating evenementen. Evenementen it already created are left
+		/// as they are.
+		#[pallet::weight(T::WeightInfo::ban_organisator())]
+		pub fn ban_organisator(origin: OriginFor<T>, organisator: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if <GebandeOrganisatoren<T>>::contains_key(&organisator) {
+				Err(Error::<T>::OrganizerBanned)?
+			}
+
+			<GebandeOrganisatoren<T>>::insert(&organisator, ());
+
+			Self::deposit_event(Event::OrganisatorGebannen(organisator));
+			Ok(())
+		}
+
+		/// Lifts the ban of `organisator`.
+		#[pallet::weight(T::WeightInfo::unban_organisator())]
+		pub fn unban_organisator(
+			origin: OriginFor<T>,
+			organisator: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if <GebandeOrganisatoren<T>>::take(&organisator).is_none() {
+				Err(Error::<T>::OrganizerNotBanned)?
+			}
+
+			Self::deposit_event(Event::OrganisatorToegelaten(organisator));
+			Ok(())
+		}
+
+		/// Moves the ticket of `klant_oud` to `klant_nieuw`, together with its scan status, scan
+		/// log and name, for instance after the phone holding it was stolen. A freeze of the ticket is
+		/// lifted.
+		#[pallet::weight(T::WeightInfo::force_transfer_ticket())]
+		pub fn force_transfer_ticket(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_oud: KlantId,
+			klant_nieuw: KlantId,
+			reden: T::Hash,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if !<Tickets<T>>::contains_key(evenement_id, klant_oud) {
+				Err(Error::<T>::TicketNotFound)?
+			}
+
+			if <Tickets<T>>::contains_key(evenement_id, klant_nieuw) {
+				Err(Error::<T>::TicketAlreadyOwned)?
+			}
+
+			<Tickets<T>>::swap(evenement_id, klant_oud, evenement_id, klant_nieuw);
+			<ScanRegistraties<T>>::swap(evenement_id, klant_oud, evenement_id, klant_nieuw);
+			<TicketNamen<T>>::swap(evenement_id, klant_oud, evenement_id, klant_nieuw);
+			<BevrorenTickets<T>>::remove(evenement_id, klant_oud);
+
+			Self::deposit_event(Event::TicketOvergedragen(
+				evenement_id,
+				klant_oud,
+				klant_nieuw,
+				reden,
+			));
+			Ok(())
+		}
+
+		/// Marks a scanned ticket as not scanned, for instance after a scan by mistake. The scan
+		/// log is kept.
+		#[pallet::weight(T::WeightInfo::force_unscan())]
+		pub fn force_unscan(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_id: KlantId,
+			reden: T::Hash,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if !<Tickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketNotFound)?
+			}
+
+			let mut ticket = <Tickets<T>>::get(evenement_id, klant_id);
+
+			if !ticket.is_gescand {
+				Err(Error::<T>::TicketNotScanned)?
+			}
+
+			ticket.is_gescand = false;
+
+			<Tickets<T>>::insert(evenement_id, klant_id, &ticket);
+			<Aanwezigen<T>>::mutate(evenement_id, |aanwezigen| {
+				*aanwezigen = aanwezigen.saturating_sub(1)
+			});
+
+			Self::deposit_event(Event::ScanIngetrokken(evenement_id, klant_id, reden));
+			Ok(())
+		}
+
+		/// Removes an evenement with all of its tickets, for instance when its organizer
+		/// disappeared. Tickets are removed in chunks, so the call has to be repeated until
+		/// `EvenementGeannuleerd` is emitted. The deposit is returned to the organizer.
+		#[pallet::weight(T::WeightInfo::force_cancel_evenement(T::MaxOpruimPerAanroep::get()))]
+		pub fn force_cancel_evenement(
+			origin: OriginFor<T>,
+			id: EvenementId,
+			reden: T::Hash,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if !Self::verwijder_tickets(id) {
+				return Ok(())
+			}
+
+			T::Currency::unreserve(&evenement.organisator, <Borgen<T>>::take(id));
+			let verkocht = <Verkocht<T>>::take(id);
+			<Aanwezigen<T>>::remove(id);
+			<Evenementen<T>>::remove(id);
+			<Metadata<T>>::remove(id);
+			<Regels<T>>::remove(id);
+			<Voorverkopen<T>>::remove(id);
+
+			Self::deposit_event(Event::EvenementGeannuleerd(id, verkocht, reden));
+			Ok(())
+		}
+
+		/// Freezes a ticket, so it can no longer be scanned or sold, for instance after it was
+		/// stolen or its payment was charged back. Either the admin origin or the organizer of the
+		/// evenement can freeze tickets.
+		#[pallet::weight(T::WeightInfo::freeze_ticket())]
+		pub fn freeze_ticket(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_id: KlantId,
+			reden: T::Hash,
+		) -> DispatchResult {
+			Self::ensure_admin_or_organisator(origin, evenement_id)?;
+
+			if !<Tickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketNotFound)?
+			}
+
+			if <BevrorenTickets<T>>::contains_key(evenement_id, klant_id) {
+				Err(Error::<T>::TicketAlreadyFrozen)?
+			}
+
+			<BevrorenTickets<T>>::insert(evenement_id, klant_id, reden);
+
+			Self::deposit_event(Event::TicketBevroren(evenement_id, klant_id, reden));
+			Ok(())
+		}
+
+		/// Lifts the freeze of a ticket. Either the admin origin or the organizer of the evenement
+		/// can unfreeze tickets.
+		#[pallet::weight(T::WeightInfo::unfreeze_ticket())]
+		pub fn unfreeze_ticket(
+			origin: OriginFor<T>,
+			evenement_id: EvenementId,
+			klant_id: KlantId,
+			reden: T::Hash,
+		) -> DispatchResult {
+			Self::ensure_admin_or_organisator(origin, evenement_id)?;
+
+			if <BevrorenTickets<T>>::take(evenement_id, klant_id).is_none() {
+				Err(Error::<T>::TicketNotFrozen)?
+			}
+
+			Self::deposit_event(Event::TicketVrijgegeven(evenement_id, klant_id, reden));
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Checks that the ticket of `klant_oud` can be passed on under the transfer policy and
+		/// rules of `evenement`, to a klant with name hash `naam` if known.
+		fn controleer_overdracht(
+			evenement: &EvenementOf<T>,
+			klant_oud: KlantId,
+			naam: Option<T::Hash>,
+		) -> DispatchResult {
+			if evenement.transfer_beleid == TransferBeleid::Onoverdraagbaar {
+				Err(Error::<T>::TicketNotTransferable)?
+			}
+
+			let regels = match <Regels<T>>::get(evenement.id) {
+				Some(regels) => regels,
+				None => return Ok(()),
+			};
+
+			if let Some(uren) = regels.sluiting_uren {
+				let nu = T::Tijd::now().as_millis() as Tijdstip;
+				if nu.saturating_add(uren as Tijdstip * 60 * 60 * 1000) >= evenement.begin {
+					Err(Error::<T>::TransferDeadlinePassed)?
+				}
+			}
+
+			if regels.naam_verplicht &&
+				(naam.is_none() || naam != <TicketNamen<T>>::get(evenement.id, klant_oud))
+			{
+				Err(Error::<T>::NameMismatch)?
+			}
+
+			Ok(())
+		}
+
+		/// Checks that `code` is a voucher of `evenement` that can still be redeemed, and returns
+		/// the hash of the code and the price of a ticket after the discount.
+		fn controleer_voucher(
+			evenement: &EvenementOf<T>,
+			code: &[u8],
+		) -> Result<(T::Hash, Prijs), DispatchError> {
+			let code_hash = Self::code_hash(evenement.id, code);
+
+			let voucher = match <Vouchers<T>>::get(evenement.id, code_hash) {
+				Some(voucher) => voucher,
+				None => Err(Error::<T>::VoucherNotFound)?,
+			};
+
+			if T::Tijd::now().as_millis() as Tijdstip >= voucher.verloopt {
+				Err(Error::<T>::VoucherExpired)?
+			}
+
+			if <Inwisselingen<T>>::get(evenement.id, code_hash) >= voucher.max_gebruik {
+				Err(Error::<T>::VoucherExhausted)?
+			}
+
+			let prijs = match voucher.korting {
+				Korting::Percentage(deel) => evenement.prijs.saturating_sub(deel * evenement.prijs),
+				Korting::Bedrag(bedrag) => evenement.prijs.saturating_sub(bedrag),
+			};
+
+			Ok((code_hash, prijs))
+		}
+
+		/// Whether `bewijs` proves that `koper` is a leaf of the allowlist with Merkle root
+		/// `merkle_root`.
+		fn op_toegangslijst(
+			merkle_root: &T::Hash,
+			koper: &T::AccountId,
+			bewijs: Option<Bewijs<T>>,
+		) -> bool {
+			match bewijs {
+				Some(bewijs) => Self::merkle_root(koper, &bewijs) == *merkle_root,
+				None => false,
+			}
+		}
+
+		/// The hash under which the voucher with `code` of an evenement is stored. The evenement id
+		/// salts the hash, so the same code hashes differently for every evenement.
+		pub fn code_hash(evenement_id: EvenementId, code: &[u8]) -> T::Hash {
+			T::Hashing::hash_of(&(evenement_id, code))
+		}
+
+		/// The Merkle root of an allowlist with `koper` as leaf and `bewijs` as the siblings on
+		/// the path from the leaf to the root.
+		pub(crate) fn merkle_root(koper: &T::AccountId, bewijs: &[T::Hash]) -> T::Hash {
+			bewijs.iter().fold(T::Hashing::hash_of(koper), |knoop, buur| {
+				if knoop <= *buur {
+					T::Hashing::hash_of(&(knoop, buur))
+				} else {
+					T::Hashing::hash_of(&(buur, knoop))
+				}
+			})
+		}
+
+		/// Whether the ticket of `klant_id` is frozen.
+		pub fn is_bevroren(evenement_id: EvenementId, klant_id: KlantId) -> bool {
+			<BevrorenTickets<T>>::contains_key(evenement_id, klant_id)
+		}
+
+		/// Succeeds for the admin origin and for the organizer of evenement `id`.
+		fn ensure_admin_or_organisator(origin: OriginFor<T>, id: EvenementId) -> DispatchResult {
+			let origin = match T::AdminOrigin::try_origin(origin) {
+				Ok(_) => return Ok(()),
+				Err(origin) => origin,
+			};
+			let wie = ensure_signed(origin)?;
+
+			let evenement = <Evenementen<T>>::get(id);
+
+			if evenement.id == 0 {
+				Err(Error::<T>::EvenementNotFound)?
+			}
+
+			if evenement.organisator != wie {
+				Err(Error::<T>::NotOrganizer)?
+			}
+
+			Ok(())
+		}
+
 		pub fn get_beschikbaarheid(id: EvenementId) -> u128 {
 			let evenement = <Evenementen<T>>::get(id);
 
-			let mut count = 0;
-			for ticket in <Tickets<T>>::iter_keys() {
-				if ticket.0 == evenement.id {
-					count += 1;
+			let result = evenement.aantal_tickets.saturating_sub(<Verkocht<T>>::get(id));
+			return result;
+		}
+
+		/// The deposit owed for an evenement and, if set, its metadata.
+		pub fn borg_voor(
+			evenement: &EvenementOf<T>,
+			metadata: Option<&EvenementMetadataOf<T>>,
+		) -> BalanceOf<T> {
+			let bytes = evenement.naam.len() + metadata.map_or(0, |m| m.encoded_size());
+
+			T::EvenementBorg::get()
+				.saturating_add(T::BorgPerByte::get().saturating_mul(bytes.saturated_into()))
+				.saturating_add(
+					T::TicketBorg::get().saturating_mul(evenement.aantal_tickets.saturated_into()),
+				)
+		}
+
+		/// Reserves or unreserves the difference between the current deposit of an evenement and
+		/// `nieuwe_borg`.
+		fn pas_borg_aan(evenement: &EvenementOf<T>, nieuwe_borg: BalanceOf<T>) -> DispatchResult {
+			let oude_borg = <Borgen<T>>::get(evenement.id);
+
+			if nieuwe_borg > oude_borg {
+				T::Currency::reserve(&evenement.organisator, nieuwe_borg - oude_borg)?;
+			} else {
+				T::Currency::unreserve(&evenement.organisator, oude_borg - nieuwe_borg);
+			}
+
+			<Borgen<T>>::insert(evenement.id, nieuwe_borg);
+			Ok(())
+		}
+
+		/// Removes up to `MaxOpruimPerAanroep` tickets, scan logs, freezes, names, presale
+		/// purchases, redemptions and vouchers of evenement `id` in total, and returns whether none
+		/// are left.
+		fn verwijder_tickets(id: EvenementId) -> bool {
+			// Every map is drained with what the maps before it left of the budget. Draining sees
+			// what earlier calls in the same block removed, so no call repeats their work.
+			// Redemptions go before vouchers, so none are left without their voucher.
+			let mut budget = T::MaxOpruimPerAanroep::get() as usize;
+			budget -= <Tickets<T>>::drain_prefix(id).take(budget).count();
+			budget -= <ScanRegistraties<T>>::drain_prefix(id).take(budget).count();
+			budget -= <BevrorenTickets<T>>::drain_prefix(id).take(budget).count();
+			budget -= <TicketNamen<T>>::drain_prefix(id).take(budget).count();
+			budget -= <VoorverkoopAankopen<T>>::drain_prefix(id).take(budget).count();
+			budget -= <Inwisselingen<T>>::drain_prefix(id).take(budget).count();
+			budget -= <Vouchers<T>>::drain_prefix(id).take(budget).count();
+
+			// With budget left over every map ran empty before it was used up.
+			budget > 0 ||
+				(<Tickets<T>>::iter_prefix(id).next().is_none() &&
+					<ScanRegistraties<T>>::iter_prefix(id).next().is_none() &&
+					<BevrorenTickets<T>>::iter_prefix(id).next().is_none() &&
+					<TicketNamen<T>>::iter_prefix(id).next().is_none() &&
+					<VoorverkoopAankopen<T>>::iter_prefix(id).next().is_none() &&
+					<Inwisselingen<T>>::iter_prefix(id).next().is_none() &&
+					<Vouchers<T>>::iter_prefix(id).next().is_none())
+		}
+
+		/// Removes up to `MaxOpruimPerAanroep` tickets and related entries of `evenement` and,
+		/// once none are left, the evenement itself, rewarding `opruimer` from its deposit. Has to
+		/// run in a transaction, as storage is removed before the reward is paid.
+		pub(crate) fn ruim_op(evenement: EvenementOf<T>, opruimer: T::AccountId) -> DispatchResult {
+			let id = evenement.id;
+
+			if !Self::verwijder_tickets(id) {
+				return Ok(())
+			}
+
+			let borg = <Borgen<T>>::get(id);
+			let beloning = T::OpruimBeloning::get() * borg;
+			T::Currency::repatriate_reserved(
+				&evenement.organisator,
+				&opruimer,
+				beloning,
+				BalanceStatus::Free,
+			)?;
+			T::Currency::unreserve(&evenement.organisator, borg.saturating_sub(beloning));
+			<Borgen<T>>::remove(id);
+
+			let verkocht = <Verkocht<T>>::take(id);
+			let gescand = <Aanwezigen<T>>::take(id);
+			<Evenementen<T>>::remove(id);
+			<Metadata<T>>::remove(id);
+			<Regels<T>>::remove(id);
+			<Voorverkopen<T>>::remove(id);
+
+			Self::deposit_event(Event::EvenementOpgeruimd(
+				id, verkocht, gescand, opruimer, beloning,
+			));
+			Ok(())
+		}
+
+		/// Checks that the stored evenementen, tickets and deposits are consistent with each
+		/// other. Meant for tests, fuzzing and migrations; it iterates over all of storage.
+		#[cfg(any(feature = "std", feature = "try-runtime"))]
+		pub fn controleer_integriteit() -> Result<(), &'static str> {
+			for (id, evenement) in <Evenementen<T>>::iter() {
+				if evenement.id != id {
+					Err("evenement stored under another id")?
+				}
+
+				// A partially purged evenement can have fewer tickets than it sold.
+				let verkocht = <Verkocht<T>>::get(id);
+				if (<Tickets<T>>::iter_prefix(id).count() as u128) > verkocht {
+					Err("more tickets than sold")?
+				}
+				if verkocht > evenement.aantal_tickets {
+					Err("more tickets sold than available")?
+				}
+				if <Aanwezigen<T>>::get(id) > verkocht {
+					Err("more attendees than sold tickets")?
+				}
+				// A partially purged evenement can have fewer scanned tickets than attendees.
+				let gescand = <Tickets<T>>::iter_prefix_values(id).filter(|t| t.is_gescand).count();
+				if gescand as u128 > <Aanwezigen<T>>::get(id) {
+					Err("scanned tickets not counted as attendees")?
+				}
+
+				// Evenementen migrated from before deposits existed have none.
+				let borg = <Borgen<T>>::get(id);
+				if !borg.is_zero() &&
+					borg != Self::borg_voor(&evenement, <Metadata<T>>::get(id).as_ref())
+				{
+					Err("deposit does not match evenement")?
+				}
+				if T::Currency::reserved_balance(&evenement.organisator) < borg {
+					Err("deposit is not reserved")?
 				}
 			}
 
-			let result = evenement.aantal_tickets - count;
-			return result;
+			for (id, _) in <Tickets<T>>::iter_keys() {
+				if !<Evenementen<T>>::contains_key(id) {
+					Err("ticket without evenement")?
+				}
+			}
+			for (id, _) in <ScanRegistraties<T>>::iter_keys() {
+				if !<Evenementen<T>>::contains_key(id) {
+					Err("scan log without evenement")?
+				}
+			}
+			for (id, klant_id) in <BevrorenTickets<T>>::iter_keys() {
+				if !<Tickets<T>>::contains_key(id, klant_id) {
+					Err("freeze without ticket")?
+				}
+			}
+			for (id, klant_id) in <TicketNamen<T>>::iter_keys() {
+				if !<Tickets<T>>::contains_key(id, klant_id) {
+					Err("name without ticket")?
+				}
+			}
+			for (id, code_hash, voucher) in <Vouchers<T>>::iter() {
+				if !<Evenementen<T>>::contains_key(id) {
+					Err("voucher without evenement")?
+				}
+				if <Inwisselingen<T>>::get(id, code_hash) > voucher.max_gebruik {
+					Err("voucher redeemed too often")?
+				}
+			}
+			for (id, code_hash) in <Inwisselingen<T>>::iter_keys() {
+				if !<Vouchers<T>>::contains_key(id, code_hash) {
+					Err("redemptions without voucher")?
+				}
+			}
+			for id in <Voorverkopen<T>>::iter_keys() {
+				if !<Evenementen<T>>::contains_key(id) {
+					Err("presale without evenement")?
+				}
+			}
+			for (id, _, aankopen) in <VoorverkoopAankopen<T>>::iter() {
+				if !<Evenementen<T>>::contains_key(id) {
+					Err("presale purchases without evenement")?
+				}
+				if aankopen as u128 > <Verkocht<T>>::get(id) {
+					Err("more presale purchases than sold tickets")?
+				}
+			}
+
+			Ok(())
 		}
 	}
 }
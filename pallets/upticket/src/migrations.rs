@@ -0,0 +1,157 @@
+//! Storage migrations of the upticket pallet.
+
+pub mod v1 {
+	//! Migrates from the unversioned layout of the first release to storage version 1.
+	//!
+	//! - `Evenementen` gains an organisator, an end time and a transfer policy, which is capped
+	//!   resale as before. Names are bounded.
+	//! - `Tickets` moves from a map keyed by `(evenement_id, klant_id)` to a double map.
+	//! - `Verkocht` and `Aanwezigen` are counted from the migrated tickets.
+	//!
+	//! Migrated evenementen carry no deposit and never begin or end, so they cannot be purged and
+	//! their transfers never close. Without an
+	//! organisator to assign them to the migration fails, leaving the storage as it was.
+
+	use crate::{
+		AantalTickets, Aanwezigen, Config, Evenement, EvenementId, Evenementen, KlantId, MaxPrijs,
+		Naam, Pallet, Prijs, Ticket, Tickets, Tijdstip, TransferBeleid, Verkocht,
+	};
+	use codec::{Decode, Encode};
+	use frame_support::{
+		storage::migration::storage_key_iter,
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+		weights::Weight,
+		Blake2_128Concat,
+	};
+	use sp_std::{convert::TryInto, marker::PhantomData, vec::Vec};
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+	/// An evenement as stored before version 1.
+	#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+	pub struct OudEvenement {
+		pub id: EvenementId,
+		pub naam: Vec<u8>,
+		pub prijs: Prijs,
+		pub max_prijs: MaxPrijs,
+		pub aantal_tickets: AantalTickets,
+	}
+
+	/// Old evenementen, by id.
+	pub fn oude_evenementen<T: Config>(
+	) -> frame_support::storage::PrefixIterator<(EvenementId, OudEvenement)> {
+		storage_key_iter::<EvenementId, OudEvenement, Blake2_128Concat>(
+			<Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+			b"Evenementen",
+		)
+	}
+
+	/// Old tickets, keyed by `(evenement_id, klant_id)`.
+	pub fn oude_tickets<T: Config>(
+	) -> frame_support::storage::PrefixIterator<((EvenementId, KlantId), Ticket<bool>)> {
+		storage_key_iter::<(EvenementId, KlantId), Ticket<bool>, Blake2_128Concat>(
+			<Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+			b"Tickets",
+		)
+	}
+
+	/// Migrates to storage version 1, making `Organisator` the organisator of all existing
+	/// evenementen.
+	pub struct MigrateToV1<T, Organisator>(PhantomData<(T, Organisator)>);
+
+	impl<T: Config, Organisator: Get<Option<T::AccountId>>> OnRuntimeUpgrade
+		for MigrateToV1<T, Organisator>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let organisator = match Organisator::get() {
+				Some(organisator) => organisator,
+				None => {
+					log::error!(
+						target: "runtime::upticket",
+						"no organisator for the migrated evenementen, storage is left at version 0",
+					);
+					return T::DbWeight::get().reads(2)
+				},
+			};
+			let oude: Vec<_> = oude_evenementen::<T>().collect();
+			for (id, oud) in &oude {
+				let mut naam = oud.naam.clone();
+				naam.truncate(T::MaxNaamLengte::get() as usize);
+				let naam: Naam<T> = naam.try_into().unwrap_or_default();
+
+				let evenement = Evenement {
+					id: oud.id,
+					naam,
+					prijs: oud.prijs,
+					max_prijs: oud.max_prijs,
+					aantal_tickets: oud.aantal_tickets,
+					organisator: organisator.clone(),
+					begin: Tijdstip::MAX,
+					einde: Tijdstip::MAX,
+					transfer_beleid: TransferBeleid::BegrensdeDoorverkoop,
+				};
+				<Evenementen<T>>::insert(id, evenement);
+			}
+
+			// The new keys share the prefix of the old ones, so drain before inserting.
+			let tickets: Vec<_> = oude_tickets::<T>().drain().collect();
+			for ((evenement_id, klant_id), ticket) in &tickets {
+				<Verkocht<T>>::mutate(evenement_id, |verkocht| *verkocht += 1);
+				if ticket.is_gescand {
+					<Aanwezigen<T>>::mutate(evenement_id, |aanwezigen| *aanwezigen += 1);
+				}
+				<Tickets<T>>::insert(evenement_id, klant_id, ticket);
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			let evenementen = oude.len() as u64;
+			let tickets = tickets.len() as u64;
+			T::DbWeight::get()
+				.reads_writes(evenementen + 3 * tickets + 1, evenementen + 4 * tickets + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return Ok(())
+			}
+			if Organisator::get().is_none() {
+				Err("no organisator for the migrated evenementen")?
+			}
+
+			Self::set_temp_storage(<Evenementen<T>>::iter_keys().count() as u32, "evenementen");
+			Self::set_temp_storage(oude_tickets::<T>().count() as u32, "tickets");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same upgrade may have moved past version 1.
+			if Pallet::<T>::on_chain_storage_version() < 1 {
+				Err("storage version was not updated")?
+			}
+
+			if let Some(evenementen) = Self::get_temp_storage::<u32>("evenementen") {
+				if <Evenementen<T>>::iter().count() as u32 != evenementen {
+					Err("evenementen were lost in the migration")?
+				}
+			}
+			if let Some(tickets) = Self::get_temp_storage::<u32>("tickets") {
+				if <Tickets<T>>::iter().count() as u32 != tickets {
+					Err("tickets were lost in the migration")?
+				}
+				if <Verkocht<T>>::iter_values().sum::<AantalTickets>() != tickets as AantalTickets {
+					Err("sold tickets were miscounted")?
+				}
+			}
+
+			Pallet::<T>::controleer_integriteit()
+		}
+	}
+}
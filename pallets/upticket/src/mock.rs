@@ -0,0 +1,181 @@
+//! The mock runtime of the unit tests, which the fuzzer uses as well.
+
+use crate as pallet_upticket;
+use frame_support::{
+	parameter_types,
+	traits::{Everything, GenesisBuild, UnixTime},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+use std::{cell::RefCell, time::Duration};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+/// An account without any balance.
+pub const DAVE: u64 = 4;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		UpticketModule: pallet_upticket::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static NU: RefCell<u64> = RefCell::new(0);
+}
+
+/// A clock that only moves when a test tells it to.
+pub struct MockTijd;
+
+impl MockTijd {
+	pub fn zet(millis: u64) {
+		NU.with(|nu| *nu.borrow_mut() = millis);
+	}
+
+	/// Moves the clock `millis` forward.
+	pub fn verzet(millis: u64) {
+		NU.with(|nu| {
+			let mut nu = nu.borrow_mut();
+			*nu = nu.saturating_add(millis);
+		});
+	}
+}
+
+impl UnixTime for MockTijd {
+	fn now() -> Duration {
+		Duration::from_millis(NU.with(|nu| *nu.borrow()))
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_upticket::BenchmarkTijd for MockTijd {
+	fn zet(millis: u64) {
+		NU.with(|nu| *nu.borrow_mut() = millis);
+	}
+}
+
+parameter_types! {
+	pub const MaxScanRegistraties: u32 = 2;
+	pub const MaxNaamLengte: u32 = 16;
+	pub const MaxTekstLengte: u32 = 32;
+	pub const MaxArtiesten: u32 = 2;
+	pub const MaxCodeLengte: u32 = 16;
+	pub const MaxBewijsLengte: u32 = 4;
+	pub const EvenementBorg: u64 = 100;
+	pub const BorgPerByte: u64 = 1;
+	pub const TicketBorg: u64 = 1;
+	pub const OpruimTermijn: u64 = 1_000;
+	pub const OpruimBeloning: Perbill = Perbill::from_percent(10);
+	pub const MaxOpruimPerAanroep: u32 = 2;
+}
+
+impl pallet_upticket::Config for Test {
+	type Event = Event;
+	type Tijd = MockTijd;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkTijd = MockTijd;
+	type MaxScanRegistraties = MaxScanRegistraties;
+	type MaxNaamLengte = MaxNaamLengte;
+	type MaxTekstLengte = MaxTekstLengte;
+	type MaxArtiesten = MaxArtiesten;
+	type MaxCodeLengte = MaxCodeLengte;
+	type MaxBewijsLengte = MaxBewijsLengte;
+	type Currency = Balances;
+	type EvenementBorg = EvenementBorg;
+	type BorgPerByte = BorgPerByte;
+	type TicketBorg = TicketBorg;
+	type OpruimTermijn = OpruimTermijn;
+	type OpruimBeloning = OpruimBeloning;
+	type MaxOpruimPerAanroep = MaxOpruimPerAanroep;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_met(Default::default())
+}
+
+/// Like [`new_test_ext`], with `upticket` as the genesis of the pallet.
+pub fn new_test_ext_met(
+	upticket: pallet_upticket::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 100_000), (BOB, 100_000), (CHARLIE, 100_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&upticket, &mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		// Events are not deposited in the genesis block.
+		System::set_block_number(1);
+		MockTijd::zet(0);
+	});
+	ext
+}
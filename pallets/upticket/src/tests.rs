@@ -0,0 +1,1410 @@
+use crate::{
+	migrations::v1::{MigrateToV1, OudEvenement},
+	mock::*,
+	AantalTickets, Bewijs, Error, Evenement, EvenementId, EvenementMetadata, EvenementMetadataOf,
+	Evenementen, Event as UpticketEvent, KlantId, Korting, Overdrachtsregels, ScanRegistratie,
+	ScanRegistraties, Ticket, Tickets, Tijdstip, TransferBeleid, Voorverkoop, Voucher, Vouchers,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	storage::{migration, unhashed},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	Blake2_128Concat, BoundedVec, StorageHasher,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, Permill,
+};
+use std::convert::{TryFrom, TryInto};
+
+const EVENEMENT: EvenementId = 1;
+const UUR: Tijdstip = 60 * 60 * 1000;
+const BEGIN: Tijdstip = 2 * UUR;
+const EINDE: Tijdstip = BEGIN + 4 * UUR;
+
+fn tekst<S: Get<u32>>(tekst: &[u8]) -> BoundedVec<u8, S> {
+	tekst.to_vec().try_into().unwrap()
+}
+
+fn maak_evenement(aantal_tickets: AantalTickets) {
+	assert_ok!(UpticketModule::create_evenement(
+		Origin::signed(ALICE),
+		10,
+		20,
+		tekst(b"Concert"),
+		EVENEMENT,
+		aantal_tickets,
+		BEGIN,
+		EINDE,
+	));
+}
+
+fn koop_ticket(klant_id: KlantId) {
+	assert_ok!(UpticketModule::buy_ticket(
+		Origin::signed(BOB),
+		EVENEMENT,
+		klant_id,
+		false,
+		None,
+		None
+	));
+}
+
+fn metadata() -> EvenementMetadataOf<Test> {
+	EvenementMetadata {
+		locatie: tekst(b"Ziggo Dome"),
+		beschrijving: tekst(b"Een avond vol muziek"),
+		poster: tekst(b"bafyposter"),
+		voorwaarden: tekst(b"bafyvoorwaarden"),
+		artiesten: vec![tekst(b"De Dijk")].try_into().unwrap(),
+		minimum_leeftijd: Some(18),
+	}
+}
+
+#[test]
+fn genesis_config_works() {
+	new_test_ext_met(crate::GenesisConfig {
+		evenementen: vec![(EVENEMENT, ALICE, b"Concert".to_vec(), 10, 20, 3, BEGIN, EINDE)],
+		tickets: vec![(EVENEMENT, 1, false), (EVENEMENT, 2, true)],
+	})
+	.execute_with(|| {
+		assert_eq!(UpticketModule::evenementen(EVENEMENT).organisator, ALICE);
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 2);
+		assert!(UpticketModule::tickets(EVENEMENT, 2).is_gescand);
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 1);
+		assert_eq!(Balances::reserved_balance(ALICE), 110);
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+#[should_panic(expected = "More genesis tickets than the evenement has")]
+fn genesis_config_fails_for_too_many_tickets() {
+	new_test_ext_met(crate::GenesisConfig {
+		evenementen: vec![(EVENEMENT, ALICE, b"Concert".to_vec(), 10, 20, 1, BEGIN, EINDE)],
+		tickets: vec![(EVENEMENT, 1, false), (EVENEMENT, 2, false)],
+	});
+}
+
+#[test]
+fn create_evenement_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		let evenement = Evenement {
+			id: EVENEMENT,
+			naam: tekst(b"Concert"),
+			prijs: 10,
+			max_prijs: 20,
+			aantal_tickets: 3,
+			organisator: ALICE,
+			begin: BEGIN,
+			einde: EINDE,
+			transfer_beleid: TransferBeleid::BegrensdeDoorverkoop,
+		};
+		assert_eq!(UpticketModule::evenementen(EVENEMENT), evenement);
+		// Base deposit, one per byte of the name and one per ticket.
+		assert_eq!(UpticketModule::borgen(EVENEMENT), 100 + 7 + 3);
+		assert_eq!(Balances::reserved_balance(ALICE), 110);
+		System::assert_last_event(UpticketEvent::EvenementAangemaakt(evenement).into());
+	});
+}
+
+#[test]
+fn create_evenement_fails_for_existing_id() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_noop!(
+			UpticketModule::create_evenement(
+				Origin::signed(BOB),
+				10,
+				20,
+				tekst(b"Overgenomen"),
+				EVENEMENT,
+				3,
+				BEGIN,
+				EINDE,
+			),
+			Error::<Test>::EvenementAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn create_evenement_fails_for_id_zero() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::create_evenement(
+				Origin::signed(ALICE),
+				10,
+				20,
+				tekst(b"Concert"),
+				0,
+				3,
+				BEGIN,
+				EINDE,
+			),
+			Error::<Test>::InvalidEvenementId
+		);
+	});
+}
+
+#[test]
+fn create_evenement_fails_without_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::create_evenement(
+				Origin::signed(DAVE),
+				10,
+				20,
+				tekst(b"Concert"),
+				EVENEMENT,
+				3,
+				BEGIN,
+				EINDE,
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn set_evenement_metadata_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_ok!(UpticketModule::set_evenement_metadata(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			metadata()
+		));
+
+		assert_eq!(UpticketModule::metadata(EVENEMENT), Some(metadata()));
+		let borg = 110 + metadata().encoded_size() as u64;
+		assert_eq!(UpticketModule::borgen(EVENEMENT), borg);
+		assert_eq!(Balances::reserved_balance(ALICE), borg);
+		System::assert_last_event(UpticketEvent::EvenementMetadataGewijzigd(EVENEMENT).into());
+	});
+}
+
+#[test]
+fn set_evenement_metadata_fails_for_unknown_evenement() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::set_evenement_metadata(Origin::signed(ALICE), EVENEMENT, metadata()),
+			Error::<Test>::EvenementNotFound
+		);
+	});
+}
+
+#[test]
+fn set_evenement_metadata_fails_for_others() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_noop!(
+			UpticketModule::set_evenement_metadata(Origin::signed(BOB), EVENEMENT, metadata()),
+			Error::<Test>::NotOrganizer
+		);
+	});
+}
+
+#[test]
+fn remove_evenement_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		assert_ok!(UpticketModule::set_evenement_metadata(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			metadata()
+		));
+
+		assert_ok!(UpticketModule::remove_evenement(Origin::signed(ALICE), EVENEMENT));
+
+		assert!(!Evenementen::<Test>::contains_key(EVENEMENT));
+		assert_eq!(UpticketModule::metadata(EVENEMENT), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		System::assert_last_event(UpticketEvent::EvenementVerwijderd(EVENEMENT).into());
+	});
+}
+
+#[test]
+fn remove_evenement_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::remove_evenement(Origin::signed(ALICE), EVENEMENT),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		assert_noop!(
+			UpticketModule::remove_evenement(Origin::signed(BOB), EVENEMENT),
+			Error::<Test>::NotOrganizer
+		);
+
+		koop_ticket(1);
+		assert_noop!(
+			UpticketModule::remove_evenement(Origin::signed(ALICE), EVENEMENT),
+			Error::<Test>::TicketsAlreadySold
+		);
+	});
+}
+
+#[test]
+fn get_evenement_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::get_evenement(Origin::signed(BOB), EVENEMENT),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		assert_ok!(UpticketModule::get_evenement(Origin::signed(BOB), EVENEMENT));
+
+		System::assert_last_event(
+			UpticketEvent::Evenement(UpticketModule::evenementen(EVENEMENT)).into(),
+		);
+	});
+}
+
+#[test]
+fn get_evenement_beschikbaarheid_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::get_evenement_beschikbaarheid(Origin::signed(BOB), EVENEMENT),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::get_evenement_beschikbaarheid(Origin::signed(BOB), EVENEMENT));
+
+		System::assert_last_event(UpticketEvent::Beschikbaarheid(2).into());
+	});
+}
+
+#[test]
+fn buy_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		koop_ticket(1);
+
+		assert!(Tickets::<Test>::contains_key(EVENEMENT, 1));
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 1);
+		assert_eq!(UpticketModule::get_beschikbaarheid(EVENEMENT), 2);
+		System::assert_last_event(
+			UpticketEvent::TicketAangemaakt(EVENEMENT, 1, Ticket { is_gescand: false }).into(),
+		);
+	});
+}
+
+#[test]
+fn buy_ticket_counts_scanned_tickets_as_attendees() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_ok!(UpticketModule::buy_ticket(Origin::signed(BOB), EVENEMENT, 1, true, None, None));
+
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 1);
+		// The scan can be undone without the attendance dropping below zero.
+		assert_ok!(UpticketModule::force_unscan(Origin::root(), EVENEMENT, 1, reden()));
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 0);
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+fn buy_ticket_fails_for_unknown_evenement() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::buy_ticket(Origin::signed(BOB), EVENEMENT, 1, false, None, None),
+			Error::<Test>::EvenementNotFound
+		);
+	});
+}
+
+#[test]
+fn buy_ticket_fails_when_sold_out() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(1);
+		koop_ticket(1);
+
+		assert_noop!(
+			UpticketModule::buy_ticket(Origin::signed(BOB), EVENEMENT, 2, false, None, None),
+			Error::<Test>::NoTicketsAvailable
+		);
+	});
+}
+
+#[test]
+fn buy_ticket_fails_for_owned_ticket() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert_noop!(
+			UpticketModule::buy_ticket(Origin::signed(BOB), EVENEMENT, 1, false, None, None),
+			Error::<Test>::TicketAlreadyOwned
+		);
+	});
+}
+
+#[test]
+fn scan_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		MockTijd::zet(5_000);
+
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert!(UpticketModule::tickets(EVENEMENT, 1).is_gescand);
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 1);
+		assert_eq!(
+			UpticketModule::scan_registraties(EVENEMENT, 1).into_inner(),
+			vec![ScanRegistratie { blok: 1, tijdstip: 5_000, scanner: CHARLIE }]
+		);
+		System::assert_last_event(UpticketEvent::TicketGescand(EVENEMENT, 1, CHARLIE).into());
+	});
+}
+
+#[test]
+fn scan_ticket_drops_oldest_registratie_when_log_is_full() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		let oud = |blok| ScanRegistratie { blok, tijdstip: 0, scanner: BOB };
+		ScanRegistraties::<Test>::insert(
+			EVENEMENT,
+			1,
+			BoundedVec::<_, MaxScanRegistraties>::try_from(vec![oud(0), oud(1)]).unwrap(),
+		);
+
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert_eq!(
+			UpticketModule::scan_registraties(EVENEMENT, 1).into_inner(),
+			vec![oud(1), ScanRegistratie { blok: 1, tijdstip: 0, scanner: CHARLIE }]
+		);
+	});
+}
+
+#[test]
+fn scan_ticket_fails_for_unknown_ticket() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_noop!(
+			UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1),
+			Error::<Test>::TicketNotFound
+		);
+	});
+}
+
+#[test]
+fn scan_ticket_fails_when_already_scanned() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert_noop!(
+			UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1),
+			Error::<Test>::TicketAlreadyScanned
+		);
+	});
+}
+
+#[test]
+fn sell_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		assert_ok!(UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20));
+
+		assert!(!Tickets::<Test>::contains_key(EVENEMENT, 1));
+		assert!(Tickets::<Test>::contains_key(EVENEMENT, 2));
+		System::assert_last_event(UpticketEvent::TicketVerkocht(EVENEMENT, 1, 2, 20).into());
+	});
+}
+
+#[test]
+fn sell_ticket_fails_for_unknown_evenement() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::EvenementNotFound
+		);
+	});
+}
+
+#[test]
+fn sell_ticket_fails_for_unknown_ticket() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::TicketNotFound
+		);
+	});
+}
+
+#[test]
+fn sell_ticket_fails_for_scanned_ticket() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::TicketAlreadyScanned
+		);
+	});
+}
+
+#[test]
+fn sell_ticket_fails_above_max_prijs() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 21),
+			Error::<Test>::MaxPriceExceeded
+		);
+	});
+}
+
+#[test]
+fn sell_ticket_fails_to_ticket_owner() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		koop_ticket(2);
+
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::TicketAlreadyOwned
+		);
+	});
+}
+
+#[test]
+fn transfer_beleid_is_enforced() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		assert_ok!(UpticketModule::set_transfer_beleid(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			TransferBeleid::Onoverdraagbaar,
+		));
+		System::assert_last_event(
+			UpticketEvent::TransferBeleidGewijzigd(EVENEMENT, TransferBeleid::Onoverdraagbaar)
+				.into(),
+		);
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 10),
+			Error::<Test>::TicketNotTransferable
+		);
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None),
+			Error::<Test>::TicketNotTransferable
+		);
+
+		assert_ok!(UpticketModule::set_transfer_beleid(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			TransferBeleid::VrijeMarkt,
+		));
+		assert_ok!(UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 1_000));
+		assert_ok!(UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 2, 3, None));
+	});
+}
+
+#[test]
+fn set_transfer_beleid_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::set_transfer_beleid(
+				Origin::signed(ALICE),
+				EVENEMENT,
+				TransferBeleid::VrijeMarkt
+			),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		assert_noop!(
+			UpticketModule::set_transfer_beleid(
+				Origin::signed(BOB),
+				EVENEMENT,
+				TransferBeleid::VrijeMarkt
+			),
+			Error::<Test>::NotOrganizer
+		);
+	});
+}
+
+fn zet_regels(sluiting_uren: Option<u32>, naam_verplicht: bool) {
+	assert_ok!(UpticketModule::set_overdrachtsregels(
+		Origin::signed(ALICE),
+		EVENEMENT,
+		Some(Overdrachtsregels { sluiting_uren, naam_verplicht }),
+	));
+}
+
+#[test]
+fn transfer_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		assert_ok!(UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None));
+		System::assert_last_event(UpticketEvent::TicketGeschonken(EVENEMENT, 1, 2).into());
+		assert!(!Tickets::<Test>::contains_key(EVENEMENT, 1));
+		assert!(Tickets::<Test>::contains_key(EVENEMENT, 2));
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 1);
+	});
+}
+
+#[test]
+fn transfer_ticket_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		koop_ticket(1);
+		koop_ticket(2);
+		koop_ticket(3);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 3));
+
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 4, 5, None),
+			Error::<Test>::TicketNotFound
+		);
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 3, 5, None),
+			Error::<Test>::TicketAlreadyScanned
+		);
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None),
+			Error::<Test>::TicketAlreadyOwned
+		);
+	});
+}
+
+#[test]
+fn transfer_rules_are_enforced() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		// Transfers close an hour before the evenement begins, not before it ends.
+		zet_regels(Some(1), false);
+		MockTijd::zet(BEGIN - UUR - 1);
+		assert_ok!(UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None));
+		MockTijd::zet(BEGIN - UUR);
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 2, 3, None),
+			Error::<Test>::TransferDeadlinePassed
+		);
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 2, 3, 20),
+			Error::<Test>::TransferDeadlinePassed
+		);
+
+		assert_ok!(UpticketModule::set_overdrachtsregels(Origin::signed(ALICE), EVENEMENT, None));
+		assert_ok!(UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 2, 3, None));
+	});
+}
+
+#[test]
+fn transfer_rules_can_require_names() {
+	new_test_ext().execute_with(|| {
+		let jan = BlakeTwo256::hash(b"Jan Jansen");
+		let piet = BlakeTwo256::hash(b"Piet Pietersen");
+		maak_evenement(3);
+		koop_ticket(1);
+		zet_regels(None, true);
+
+		// A ticket without a name cannot be passed on.
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, Some(jan)),
+			Error::<Test>::NameMismatch
+		);
+
+		assert_ok!(UpticketModule::set_ticket_naam(Origin::signed(ALICE), EVENEMENT, 1, Some(jan)));
+		System::assert_last_event(UpticketEvent::TicketNaamGewijzigd(EVENEMENT, 1).into());
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, Some(piet)),
+			Error::<Test>::NameMismatch
+		);
+		assert_noop!(
+			UpticketModule::transfer_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, None),
+			Error::<Test>::NameMismatch
+		);
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::NameMismatch
+		);
+
+		// Jan moves the ticket to another klant id of his own.
+		assert_ok!(UpticketModule::transfer_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			1,
+			2,
+			Some(jan)
+		));
+		assert_eq!(UpticketModule::ticket_namen(EVENEMENT, 1), None);
+		assert_eq!(UpticketModule::ticket_namen(EVENEMENT, 2), Some(jan));
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+fn transfer_rules_are_set_by_the_organizer() {
+	new_test_ext().execute_with(|| {
+		let regels = Overdrachtsregels { sluiting_uren: Some(1), naam_verplicht: false };
+		assert_noop!(
+			UpticketModule::set_overdrachtsregels(
+				Origin::signed(ALICE),
+				EVENEMENT,
+				Some(regels.clone())
+			),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_noop!(
+			UpticketModule::set_overdrachtsregels(
+				Origin::signed(BOB),
+				EVENEMENT,
+				Some(regels.clone())
+			),
+			Error::<Test>::NotOrganizer
+		);
+		assert_noop!(
+			UpticketModule::set_ticket_naam(Origin::signed(BOB), EVENEMENT, 1, None),
+			Error::<Test>::NotOrganizer
+		);
+		assert_noop!(
+			UpticketModule::set_ticket_naam(Origin::signed(ALICE), EVENEMENT, 2, None),
+			Error::<Test>::TicketNotFound
+		);
+
+		assert_ok!(UpticketModule::set_overdrachtsregels(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			Some(regels.clone()),
+		));
+		System::assert_last_event(UpticketEvent::OverdrachtsregelsGewijzigd(EVENEMENT).into());
+		assert_eq!(UpticketModule::overdrachtsregels(EVENEMENT), Some(regels));
+	});
+}
+
+#[test]
+fn purge_evenement_works_in_chunks() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		koop_ticket(2);
+		koop_ticket(3);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+		MockTijd::zet(EINDE + 1_000);
+	});
+	// Commit like block import would, so the calls below start from stored keys.
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		assert_ok!(UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT));
+		assert!(Evenementen::<Test>::contains_key(EVENEMENT));
+		assert_eq!(Tickets::<Test>::iter_prefix(EVENEMENT).count(), 1);
+
+		// A second call in the same block carries on where the first one stopped.
+		assert_ok!(UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT));
+		assert!(!Evenementen::<Test>::contains_key(EVENEMENT));
+		assert_eq!(Tickets::<Test>::iter_prefix(EVENEMENT).count(), 0);
+		assert_eq!(ScanRegistraties::<Test>::iter_prefix(EVENEMENT).count(), 0);
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 0);
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 0);
+
+		// 10% of the 110 deposit goes to whoever purged the evenement.
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100_000 - 11);
+		assert_eq!(Balances::free_balance(BOB), 100_000 + 11);
+		System::assert_last_event(
+			UpticketEvent::EvenementOpgeruimd(EVENEMENT, 3, 1, BOB, 11).into(),
+		);
+	});
+}
+
+#[test]
+fn purge_evenement_shares_the_removal_limit_between_maps() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		maak_evenement(2);
+		koop_ticket(1);
+		koop_ticket(2);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 2));
+		MockTijd::zet(EINDE + 1_000);
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		// Both tickets use up the limit of 2, so the scan logs are left for the next call.
+		assert_ok!(UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT));
+		assert_eq!(Tickets::<Test>::iter_prefix(EVENEMENT).count(), 0);
+		assert_eq!(ScanRegistraties::<Test>::iter_prefix(EVENEMENT).count(), 2);
+		assert!(Evenementen::<Test>::contains_key(EVENEMENT));
+
+		assert_ok!(UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT));
+		assert_eq!(ScanRegistraties::<Test>::iter_prefix(EVENEMENT).count(), 0);
+		assert!(!Evenementen::<Test>::contains_key(EVENEMENT));
+	});
+}
+
+#[test]
+fn purge_evenement_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		MockTijd::zet(EINDE + 999);
+		assert_noop!(
+			UpticketModule::purge_evenement(Origin::signed(BOB), EVENEMENT),
+			Error::<Test>::EvenementNotEnded
+		);
+	});
+}
+
+#[test]
+fn ban_organisator_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(UpticketModule::ban_organisator(Origin::root(), ALICE));
+		System::assert_last_event(UpticketEvent::OrganisatorGebannen(ALICE).into());
+		assert_noop!(
+			UpticketModule::create_evenement(
+				Origin::signed(ALICE),
+				10,
+				20,
+				tekst(b"Concert"),
+				EVENEMENT,
+				3,
+				BEGIN,
+				EINDE,
+			),
+			Error::<Test>::OrganizerBanned
+		);
+
+		assert_ok!(UpticketModule::unban_organisator(Origin::root(), ALICE));
+		System::assert_last_event(UpticketEvent::OrganisatorToegelaten(ALICE).into());
+		maak_evenement(3);
+	});
+}
+
+#[test]
+fn ban_organisator_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UpticketModule::ban_organisator(Origin::signed(BOB), ALICE),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			UpticketModule::unban_organisator(Origin::root(), ALICE),
+			Error::<Test>::OrganizerNotBanned
+		);
+
+		assert_ok!(UpticketModule::ban_organisator(Origin::root(), ALICE));
+		assert_noop!(
+			UpticketModule::ban_organisator(Origin::root(), ALICE),
+			Error::<Test>::OrganizerBanned
+		);
+		assert_noop!(
+			UpticketModule::unban_organisator(Origin::signed(BOB), ALICE),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+fn reden() -> H256 {
+	BlakeTwo256::hash(b"Gestolen telefoon")
+}
+
+#[test]
+fn force_transfer_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+		assert_ok!(UpticketModule::freeze_ticket(Origin::root(), EVENEMENT, 1, reden()));
+
+		assert_ok!(UpticketModule::force_transfer_ticket(Origin::root(), EVENEMENT, 1, 2, reden()));
+		System::assert_last_event(
+			UpticketEvent::TicketOvergedragen(EVENEMENT, 1, 2, reden()).into(),
+		);
+		assert!(!Tickets::<Test>::contains_key(EVENEMENT, 1));
+		assert_eq!(UpticketModule::tickets(EVENEMENT, 2), Ticket { is_gescand: true });
+		assert_eq!(UpticketModule::scan_registraties(EVENEMENT, 2).len(), 1);
+		assert_eq!(UpticketModule::bevroren_tickets(EVENEMENT, 1), None);
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+fn force_transfer_ticket_fails() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		koop_ticket(2);
+
+		assert_noop!(
+			UpticketModule::force_transfer_ticket(Origin::signed(ALICE), EVENEMENT, 1, 3, reden()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			UpticketModule::force_transfer_ticket(Origin::root(), EVENEMENT, 3, 4, reden()),
+			Error::<Test>::TicketNotFound
+		);
+		assert_noop!(
+			UpticketModule::force_transfer_ticket(Origin::root(), EVENEMENT, 1, 2, reden()),
+			Error::<Test>::TicketAlreadyOwned
+		);
+	});
+}
+
+#[test]
+fn force_unscan_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+
+		assert_ok!(UpticketModule::force_unscan(Origin::root(), EVENEMENT, 1, reden()));
+		System::assert_last_event(UpticketEvent::ScanIngetrokken(EVENEMENT, 1, reden()).into());
+		assert_eq!(UpticketModule::tickets(EVENEMENT, 1), Ticket { is_gescand: false });
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 0);
+		// The scan log is kept for audits.
+		assert_eq!(UpticketModule::scan_registraties(EVENEMENT, 1).len(), 1);
+
+		assert_noop!(
+			UpticketModule::force_unscan(Origin::root(), EVENEMENT, 1, reden()),
+			Error::<Test>::TicketNotScanned
+		);
+		assert_noop!(
+			UpticketModule::force_unscan(Origin::root(), EVENEMENT, 2, reden()),
+			Error::<Test>::TicketNotFound
+		);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+	});
+}
+
+#[test]
+fn force_cancel_evenement_works_in_chunks() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		koop_ticket(2);
+		koop_ticket(3);
+		assert_ok!(UpticketModule::freeze_ticket(Origin::root(), EVENEMENT, 1, reden()));
+	});
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		assert_ok!(UpticketModule::force_cancel_evenement(Origin::root(), EVENEMENT, reden()));
+		assert!(Evenementen::<Test>::contains_key(EVENEMENT));
+		assert_eq!(Tickets::<Test>::iter_prefix(EVENEMENT).count(), 1);
+
+		assert_ok!(UpticketModule::force_cancel_evenement(Origin::root(), EVENEMENT, reden()));
+		assert!(!Evenementen::<Test>::contains_key(EVENEMENT));
+		assert_eq!(Tickets::<Test>::iter_prefix(EVENEMENT).count(), 0);
+		assert_eq!(UpticketModule::bevroren_tickets(EVENEMENT, 1), None);
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 0);
+
+		// The whole deposit goes back to the organizer.
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100_000);
+		System::assert_last_event(
+			UpticketEvent::EvenementGeannuleerd(EVENEMENT, 3, reden()).into(),
+		);
+
+		assert_noop!(
+			UpticketModule::force_cancel_evenement(Origin::root(), EVENEMENT, reden()),
+			Error::<Test>::EvenementNotFound
+		);
+	});
+}
+
+#[test]
+fn freeze_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+
+		assert_noop!(
+			UpticketModule::freeze_ticket(Origin::signed(BOB), EVENEMENT, 1, reden()),
+			Error::<Test>::NotOrganizer
+		);
+		assert_noop!(
+			UpticketModule::freeze_ticket(Origin::signed(BOB), EVENEMENT + 1, 1, reden()),
+			Error::<Test>::EvenementNotFound
+		);
+		assert_noop!(
+			UpticketModule::freeze_ticket(Origin::root(), EVENEMENT, 2, reden()),
+			Error::<Test>::TicketNotFound
+		);
+
+		assert_ok!(UpticketModule::freeze_ticket(Origin::root(), EVENEMENT, 1, reden()));
+		System::assert_last_event(UpticketEvent::TicketBevroren(EVENEMENT, 1, reden()).into());
+		assert_eq!(UpticketModule::bevroren_tickets(EVENEMENT, 1), Some(reden()));
+
+		assert_noop!(
+			UpticketModule::freeze_ticket(Origin::root(), EVENEMENT, 1, reden()),
+			Error::<Test>::TicketAlreadyFrozen
+		);
+		assert_noop!(
+			UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1),
+			Error::<Test>::TicketFrozen
+		);
+		assert_noop!(
+			UpticketModule::sell_ticket(Origin::signed(BOB), EVENEMENT, 1, 2, 20),
+			Error::<Test>::TicketFrozen
+		);
+		assert!(UpticketModule::is_bevroren(EVENEMENT, 1));
+	});
+}
+
+#[test]
+fn unfreeze_ticket_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		koop_ticket(1);
+		// The organizer freezes a ticket of which the payment was charged back.
+		assert_ok!(UpticketModule::freeze_ticket(Origin::signed(ALICE), EVENEMENT, 1, reden()));
+
+		assert_noop!(
+			UpticketModule::unfreeze_ticket(Origin::signed(BOB), EVENEMENT, 1, reden()),
+			Error::<Test>::NotOrganizer
+		);
+		assert_ok!(UpticketModule::unfreeze_ticket(Origin::root(), EVENEMENT, 1, reden()));
+		System::assert_last_event(UpticketEvent::TicketVrijgegeven(EVENEMENT, 1, reden()).into());
+		assert!(!UpticketModule::is_bevroren(EVENEMENT, 1));
+
+		assert_noop!(
+			UpticketModule::unfreeze_ticket(Origin::signed(ALICE), EVENEMENT, 1, reden()),
+			Error::<Test>::TicketNotFrozen
+		);
+		assert_ok!(UpticketModule::scan_ticket(Origin::signed(CHARLIE), EVENEMENT, 1));
+	});
+}
+
+fn voeg_voucher_toe(code: &[u8], korting: Korting, max_gebruik: u32) {
+	assert_ok!(UpticketModule::add_voucher(
+		Origin::signed(ALICE),
+		EVENEMENT,
+		UpticketModule::code_hash(EVENEMENT, code),
+		Voucher { korting, max_gebruik, verloopt: EINDE },
+	));
+}
+
+#[test]
+fn buy_ticket_with_voucher_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		voeg_voucher_toe(b"ZOMER", Korting::Percentage(Permill::from_percent(20)), 2);
+		voeg_voucher_toe(b"GRATIS", Korting::Bedrag(15), 1);
+		let zomer = UpticketModule::code_hash(EVENEMENT, b"ZOMER");
+		let gratis = UpticketModule::code_hash(EVENEMENT, b"GRATIS");
+
+		// Only the hash of the code is stored, salted with the evenement id.
+		assert!(Vouchers::<Test>::contains_key(EVENEMENT, zomer));
+		assert!(!Vouchers::<Test>::contains_key(EVENEMENT, BlakeTwo256::hash(b"ZOMER")));
+		assert_ne!(zomer, UpticketModule::code_hash(EVENEMENT + 1, b"ZOMER"));
+
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			1,
+			false,
+			Some(tekst(b"ZOMER")),
+			None
+		));
+		System::assert_last_event(UpticketEvent::VoucherIngewisseld(EVENEMENT, 1, zomer, 8).into());
+		assert_eq!(UpticketModule::inwisselingen(EVENEMENT, zomer), 1);
+
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			2,
+			false,
+			Some(tekst(b"GRATIS")),
+			None
+		));
+		System::assert_last_event(
+			UpticketEvent::VoucherIngewisseld(EVENEMENT, 2, gratis, 0).into(),
+		);
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+fn buy_ticket_with_voucher_fails() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		voeg_voucher_toe(b"ZOMER", Korting::Percentage(Permill::from_percent(20)), 1);
+
+		assert_noop!(
+			UpticketModule::buy_ticket(
+				Origin::signed(BOB),
+				EVENEMENT,
+				1,
+				false,
+				Some(tekst(b"WINTER")),
+				None
+			),
+			Error::<Test>::VoucherNotFound
+		);
+
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			1,
+			false,
+			Some(tekst(b"ZOMER")),
+			None
+		));
+		assert_noop!(
+			UpticketModule::buy_ticket(
+				Origin::signed(BOB),
+				EVENEMENT,
+				2,
+				false,
+				Some(tekst(b"ZOMER")),
+				None
+			),
+			Error::<Test>::VoucherExhausted
+		);
+
+		voeg_voucher_toe(b"HERFST", Korting::Bedrag(5), 1);
+		MockTijd::zet(EINDE);
+		assert_noop!(
+			UpticketModule::buy_ticket(
+				Origin::signed(BOB),
+				EVENEMENT,
+				2,
+				false,
+				Some(tekst(b"HERFST")),
+				None
+			),
+			Error::<Test>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn add_voucher_fails() {
+	new_test_ext().execute_with(|| {
+		let voucher = Voucher { korting: Korting::Bedrag(5), max_gebruik: 1, verloopt: EINDE };
+		assert_noop!(
+			UpticketModule::add_voucher(Origin::signed(ALICE), EVENEMENT, reden(), voucher.clone()),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		assert_noop!(
+			UpticketModule::add_voucher(Origin::signed(BOB), EVENEMENT, reden(), voucher.clone()),
+			Error::<Test>::NotOrganizer
+		);
+
+		assert_ok!(UpticketModule::add_voucher(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			reden(),
+			voucher.clone()
+		));
+		System::assert_last_event(UpticketEvent::VoucherToegevoegd(EVENEMENT, reden()).into());
+		assert_noop!(
+			UpticketModule::add_voucher(Origin::signed(ALICE), EVENEMENT, reden(), voucher),
+			Error::<Test>::VoucherAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn remove_voucher_works() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		voeg_voucher_toe(b"ZOMER", Korting::Bedrag(5), 1);
+		let code_hash = UpticketModule::code_hash(EVENEMENT, b"ZOMER");
+
+		// An evenement cannot be removed while it has vouchers.
+		assert_noop!(
+			UpticketModule::remove_evenement(Origin::signed(ALICE), EVENEMENT),
+			Error::<Test>::VouchersRemaining
+		);
+		assert_noop!(
+			UpticketModule::remove_voucher(Origin::signed(BOB), EVENEMENT, code_hash),
+			Error::<Test>::NotOrganizer
+		);
+
+		assert_ok!(UpticketModule::remove_voucher(Origin::signed(ALICE), EVENEMENT, code_hash));
+		System::assert_last_event(UpticketEvent::VoucherVerwijderd(EVENEMENT, code_hash).into());
+		assert!(!Vouchers::<Test>::contains_key(EVENEMENT, code_hash));
+		assert_noop!(
+			UpticketModule::remove_voucher(Origin::signed(ALICE), EVENEMENT, code_hash),
+			Error::<Test>::VoucherNotFound
+		);
+
+		assert_ok!(UpticketModule::remove_evenement(Origin::signed(ALICE), EVENEMENT));
+	});
+}
+
+/// Hashes two nodes of an allowlist in sorted order.
+fn knoop(a: H256, b: H256) -> H256 {
+	if a <= b {
+		BlakeTwo256::hash_of(&(a, b))
+	} else {
+		BlakeTwo256::hash_of(&(b, a))
+	}
+}
+
+#[test]
+fn presale_is_limited_to_the_allowlist() {
+	new_test_ext().execute_with(|| {
+		maak_evenement(3);
+		// An allowlist of BOB, CHARLIE and two other fans.
+		let bladeren: Vec<H256> = [BOB, CHARLIE, 5, 6].iter().map(BlakeTwo256::hash_of).collect();
+		let rechts = knoop(bladeren[2], bladeren[3]);
+		let merkle_root = knoop(knoop(bladeren[0], bladeren[1]), rechts);
+		assert_ok!(UpticketModule::set_voorverkoop(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			Some(Voorverkoop { merkle_root, einde: 5_000, max_per_lid: 1 })
+		));
+		System::assert_last_event(UpticketEvent::VoorverkoopGewijzigd(EVENEMENT).into());
+
+		let bewijs: Bewijs<Test> = vec![bladeren[1], rechts].try_into().unwrap();
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			1,
+			false,
+			None,
+			Some(bewijs.clone())
+		));
+		assert_eq!(UpticketModule::voorverkoop_aankopen(EVENEMENT, BOB), 1);
+
+		// Members cannot buy more tickets than the limit, whatever klant they buy for.
+		assert_noop!(
+			UpticketModule::buy_ticket(
+				Origin::signed(BOB),
+				EVENEMENT,
+				3,
+				false,
+				None,
+				Some(bewijs.clone())
+			),
+			Error::<Test>::PresaleLimitReached
+		);
+
+		// BOB's proof does not work for DAVE, who is not on the allowlist.
+		assert_noop!(
+			UpticketModule::buy_ticket(
+				Origin::signed(DAVE),
+				EVENEMENT,
+				2,
+				false,
+				None,
+				Some(bewijs.clone())
+			),
+			Error::<Test>::NotOnAllowlist
+		);
+		assert_noop!(
+			UpticketModule::buy_ticket(Origin::signed(CHARLIE), EVENEMENT, 2, false, None, None),
+			Error::<Test>::NotOnAllowlist
+		);
+
+		// Everyone can buy once the public sale has started, without a limit.
+		MockTijd::zet(5_000);
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(DAVE),
+			EVENEMENT,
+			2,
+			false,
+			None,
+			None
+		));
+		assert_ok!(UpticketModule::buy_ticket(
+			Origin::signed(BOB),
+			EVENEMENT,
+			3,
+			false,
+			None,
+			Some(bewijs)
+		));
+		assert_eq!(UpticketModule::voorverkoop_aankopen(EVENEMENT, BOB), 1);
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+	});
+}
+
+#[test]
+fn set_voorverkoop_works() {
+	new_test_ext().execute_with(|| {
+		let voorverkoop = Voorverkoop { merkle_root: reden(), einde: 5_000, max_per_lid: 2 };
+		assert_noop!(
+			UpticketModule::set_voorverkoop(
+				Origin::signed(ALICE),
+				EVENEMENT,
+				Some(voorverkoop.clone())
+			),
+			Error::<Test>::EvenementNotFound
+		);
+
+		maak_evenement(3);
+		assert_noop!(
+			UpticketModule::set_voorverkoop(
+				Origin::signed(BOB),
+				EVENEMENT,
+				Some(voorverkoop.clone())
+			),
+			Error::<Test>::NotOrganizer
+		);
+
+		assert_ok!(UpticketModule::set_voorverkoop(
+			Origin::signed(ALICE),
+			EVENEMENT,
+			Some(voorverkoop.clone())
+		));
+		assert_eq!(UpticketModule::voorverkopen(EVENEMENT), Some(voorverkoop));
+
+		// Ending the presale opens the public sale early.
+		assert_ok!(UpticketModule::set_voorverkoop(Origin::signed(ALICE), EVENEMENT, None));
+		assert_eq!(UpticketModule::voorverkopen(EVENEMENT), None);
+		koop_ticket(1);
+	});
+}
+
+parameter_types! {
+	pub const MigratieOrganisator: Option<u64> = Some(CHARLIE);
+	pub const GeenOrganisator: Option<u64> = None;
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<UpticketModule>();
+		let oud = OudEvenement {
+			id: EVENEMENT,
+			naam: b"Een naam die te lang is".to_vec(),
+			prijs: 10,
+			max_prijs: 20,
+			aantal_tickets: 3,
+		};
+		unhashed::put(&Evenementen::<Test>::hashed_key_for(EVENEMENT), &oud);
+		for (klant_id, is_gescand) in vec![(1 as KlantId, false), (2, true)] {
+			migration::put_storage_value(
+				b"UpticketModule",
+				b"Tickets",
+				&Blake2_128Concat::hash(&(EVENEMENT, klant_id).encode()),
+				Ticket { is_gescand },
+			);
+		}
+
+		MigrateToV1::<Test, MigratieOrganisator>::on_runtime_upgrade();
+
+		assert_eq!(UpticketModule::on_chain_storage_version(), 1);
+		assert_eq!(
+			UpticketModule::evenementen(EVENEMENT),
+			Evenement {
+				id: EVENEMENT,
+				naam: tekst::<MaxNaamLengte>(b"Een naam die te "),
+				prijs: 10,
+				max_prijs: 20,
+				aantal_tickets: 3,
+				organisator: CHARLIE,
+				begin: Tijdstip::MAX,
+				einde: Tijdstip::MAX,
+				transfer_beleid: TransferBeleid::BegrensdeDoorverkoop,
+			}
+		);
+		assert_eq!(Tickets::<Test>::iter().count(), 2);
+		assert_eq!(UpticketModule::tickets(EVENEMENT, 1), Ticket { is_gescand: false });
+		assert_eq!(UpticketModule::tickets(EVENEMENT, 2), Ticket { is_gescand: true });
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 2);
+		assert_eq!(UpticketModule::aanwezigen(EVENEMENT), 1);
+	});
+}
+
+#[test]
+fn migrate_to_v1_fails_without_organisator() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<UpticketModule>();
+		let oud = OudEvenement {
+			id: EVENEMENT,
+			naam: b"Concert".to_vec(),
+			prijs: 10,
+			max_prijs: 20,
+			aantal_tickets: 3,
+		};
+		unhashed::put(&Evenementen::<Test>::hashed_key_for(EVENEMENT), &oud);
+
+		MigrateToV1::<Test, GeenOrganisator>::on_runtime_upgrade();
+
+		assert_eq!(UpticketModule::on_chain_storage_version(), 0);
+		assert_eq!(unhashed::get(&Evenementen::<Test>::hashed_key_for(EVENEMENT)), Some(oud));
+	});
+}
+
+#[test]
+fn migrate_to_v1_skips_migrated_storage() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<UpticketModule>();
+		maak_evenement(3);
+		koop_ticket(1);
+		let evenement = UpticketModule::evenementen(EVENEMENT);
+
+		MigrateToV1::<Test, MigratieOrganisator>::on_runtime_upgrade();
+
+		assert_eq!(UpticketModule::evenementen(EVENEMENT), evenement);
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 1);
+		assert!(Tickets::<Test>::contains_key(EVENEMENT, 1));
+	});
+}
+
+#[test]
+fn migrated_evenement_keeps_working() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<UpticketModule>();
+		let oud = OudEvenement {
+			id: EVENEMENT,
+			naam: b"Concert".to_vec(),
+			prijs: 10,
+			max_prijs: 20,
+			aantal_tickets: 3,
+		};
+		unhashed::put(&Evenementen::<Test>::hashed_key_for(EVENEMENT), &oud);
+
+		MigrateToV1::<Test, MigratieOrganisator>::on_runtime_upgrade();
+
+		assert_eq!(UpticketModule::controleer_integriteit(), Ok(()));
+		koop_ticket(1);
+		assert_eq!(UpticketModule::verkocht(EVENEMENT), 1);
+	});
+}
@@ -0,0 +1,422 @@
+//! Weights for pallet_upticket
+//!
+//! These weights are hand-written placeholders, not benchmark results. The storage accesses listed
+//! with each function match the extrinsic, but the constants are estimates. Replace this file with
+//! the output of `scripts/benchmark.sh` on reference hardware before launching a network, and again
+//! after changing any extrinsic of the pallet. The script builds the node with benchmarks and runs
+//!
+//! ```text
+//! ./target/release/node-template benchmark \
+//!     --chain=dev \
+//!     --steps=50 \
+//!     --repeat=20 \
+//!     --pallet=pallet_upticket \
+//!     --extrinsic='*' \
+//!     --execution=wasm \
+//!     --wasm-execution=compiled \
+//!     --heap-pages=4096 \
+//!     --template=./.maintain/frame-weight-template.hbs \
+//!     --output=./pallets/upticket/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_upticket.
+pub trait WeightInfo {
+	fn create_evenement(n: u32, ) -> Weight;
+	fn set_evenement_metadata(b: u32, a: u32, ) -> Weight;
+	fn remove_evenement() -> Weight;
+	fn purge_evenement(t: u32, ) -> Weight;
+	fn get_evenement() -> Weight;
+	fn get_evenement_beschikbaarheid() -> Weight;
+	fn buy_ticket(p: u32, ) -> Weight;
+	fn scan_ticket() -> Weight;
+	fn sell_ticket() -> Weight;
+	fn transfer_ticket() -> Weight;
+	fn set_transfer_beleid() -> Weight;
+	fn set_overdrachtsregels() -> Weight;
+	fn set_ticket_naam() -> Weight;
+	fn set_voorverkoop() -> Weight;
+	fn add_voucher() -> Weight;
+	fn remove_voucher() -> Weight;
+	fn ban_organisator() -> Weight;
+	fn unban_organisator() -> Weight;
+	fn force_transfer_ticket() -> Weight;
+	fn force_unscan() -> Weight;
+	fn force_cancel_evenement(t: u32, ) -> Weight;
+	fn freeze_ticket() -> Weight;
+	fn unfreeze_ticket() -> Weight;
+}
+
+/// Estimated weights for pallet_upticket on the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: UpticketModule GebandeOrganisatoren (r:1 w:0)
+	// Storage: UpticketModule Evenementen (r:1 w:1)
+	// Storage: UpticketModule Borgen (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_evenement(n: u32, ) -> Weight {
+		(44_126_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Borgen (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: UpticketModule Metadata (r:0 w:1)
+	fn set_evenement_metadata(b: u32, a: u32, ) -> Weight {
+		(39_610_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((11_000 as Weight).saturating_mul(b as Weight))
+			// Standard Error: 8_000
+			.saturating_add((1_946_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:1)
+	// Storage: UpticketModule Verkocht (r:1 w:0)
+	// Storage: UpticketModule Vouchers (r:1 w:0)
+	// Storage: UpticketModule Borgen (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: UpticketModule Metadata (r:0 w:1)
+	// Storage: UpticketModule Regels (r:0 w:1)
+	// Storage: UpticketModule Voorverkopen (r:0 w:1)
+	fn remove_evenement() -> Weight {
+		(45_482_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: UpticketModule Tickets (r:1 w:1)
+	// Storage: UpticketModule ScanRegistraties (r:1 w:1)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:1)
+	// Storage: UpticketModule TicketNamen (r:1 w:1)
+	// Storage: UpticketModule VoorverkoopAankopen (r:1 w:1)
+	// Storage: UpticketModule Inwisselingen (r:1 w:1)
+	// Storage: UpticketModule Vouchers (r:1 w:1)
+	// Storage: UpticketModule Borgen (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: UpticketModule Verkocht (r:1 w:1)
+	// Storage: UpticketModule Aanwezigen (r:1 w:1)
+	// Storage: UpticketModule Metadata (r:0 w:1)
+	// Storage: UpticketModule Regels (r:0 w:1)
+	// Storage: UpticketModule Voorverkopen (r:0 w:1)
+	fn purge_evenement(t: u32, ) -> Weight {
+		(62_904_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((1_156_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	fn get_evenement() -> Weight {
+		(18_224_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Verkocht (r:1 w:0)
+	fn get_evenement_beschikbaarheid() -> Weight {
+		(21_573_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Verkocht (r:1 w:1)
+	// Storage: UpticketModule Tickets (r:1 w:1)
+	// Storage: UpticketModule Aanwezigen (r:1 w:1)
+	// Storage: UpticketModule Voorverkopen (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: UpticketModule VoorverkoopAankopen (r:1 w:1)
+	// Storage: UpticketModule Vouchers (r:1 w:0)
+	// Storage: UpticketModule Inwisselingen (r:1 w:1)
+	fn buy_ticket(p: u32, ) -> Weight {
+		(44_093_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_286_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: UpticketModule Tickets (r:1 w:1)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: UpticketModule ScanRegistraties (r:1 w:1)
+	// Storage: UpticketModule Aanwezigen (r:1 w:1)
+	fn scan_ticket() -> Weight {
+		(37_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Tickets (r:2 w:2)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:0)
+	// Storage: UpticketModule Regels (r:1 w:0)
+	// Storage: UpticketModule TicketNamen (r:0 w:1)
+	fn sell_ticket() -> Weight {
+		(36_847_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Tickets (r:2 w:2)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:0)
+	// Storage: UpticketModule Regels (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: UpticketModule TicketNamen (r:1 w:2)
+	fn transfer_ticket() -> Weight {
+		(42_613_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:1)
+	fn set_transfer_beleid() -> Weight {
+		(23_105_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Regels (r:0 w:1)
+	fn set_overdrachtsregels() -> Weight {
+		(21_948_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Tickets (r:1 w:0)
+	// Storage: UpticketModule TicketNamen (r:0 w:1)
+	fn set_ticket_naam() -> Weight {
+		(24_377_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Voorverkopen (r:0 w:1)
+	fn set_voorverkoop() -> Weight {
+		(22_361_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Vouchers (r:1 w:1)
+	fn add_voucher() -> Weight {
+		(23_915_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Vouchers (r:1 w:1)
+	// Storage: UpticketModule Inwisselingen (r:0 w:1)
+	fn remove_voucher() -> Weight {
+		(26_048_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: UpticketModule GebandeOrganisatoren (r:1 w:1)
+	fn ban_organisator() -> Weight {
+		(17_803_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule GebandeOrganisatoren (r:1 w:1)
+	fn unban_organisator() -> Weight {
+		(18_241_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Tickets (r:2 w:2)
+	// Storage: UpticketModule ScanRegistraties (r:2 w:2)
+	// Storage: UpticketModule TicketNamen (r:2 w:2)
+	// Storage: UpticketModule BevrorenTickets (r:0 w:1)
+	fn force_transfer_ticket() -> Weight {
+		(39_042_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: UpticketModule Tickets (r:1 w:1)
+	// Storage: UpticketModule Aanwezigen (r:1 w:1)
+	fn force_unscan() -> Weight {
+		(25_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:1)
+	// Storage: UpticketModule Tickets (r:1 w:1)
+	// Storage: UpticketModule ScanRegistraties (r:1 w:1)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:1)
+	// Storage: UpticketModule TicketNamen (r:1 w:1)
+	// Storage: UpticketModule VoorverkoopAankopen (r:1 w:1)
+	// Storage: UpticketModule Inwisselingen (r:1 w:1)
+	// Storage: UpticketModule Vouchers (r:1 w:1)
+	// Storage: UpticketModule Borgen (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: UpticketModule Verkocht (r:1 w:1)
+	// Storage: UpticketModule Aanwezigen (r:0 w:1)
+	// Storage: UpticketModule Metadata (r:0 w:1)
+	// Storage: UpticketModule Regels (r:0 w:1)
+	// Storage: UpticketModule Voorverkopen (r:0 w:1)
+	fn force_cancel_evenement(t: u32, ) -> Weight {
+		(51_337_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((1_149_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule Tickets (r:1 w:0)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:1)
+	fn freeze_ticket() -> Weight {
+		(27_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: UpticketModule Evenementen (r:1 w:0)
+	// Storage: UpticketModule BevrorenTickets (r:1 w:1)
+	fn unfreeze_ticket() -> Weight {
+		(24_906_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_evenement(n: u32, ) -> Weight {
+		(44_126_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_evenement_metadata(b: u32, a: u32, ) -> Weight {
+		(39_610_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((11_000 as Weight).saturating_mul(b as Weight))
+			// Standard Error: 8_000
+			.saturating_add((1_946_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn remove_evenement() -> Weight {
+		(45_482_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn purge_evenement(t: u32, ) -> Weight {
+		(62_904_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((1_156_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	fn get_evenement() -> Weight {
+		(18_224_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn get_evenement_beschikbaarheid() -> Weight {
+		(21_573_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+	fn buy_ticket(p: u32, ) -> Weight {
+		(44_093_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_286_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn scan_ticket() -> Weight {
+		(37_402_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn sell_ticket() -> Weight {
+		(36_847_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn transfer_ticket() -> Weight {
+		(42_613_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_transfer_beleid() -> Weight {
+		(23_105_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_overdrachtsregels() -> Weight {
+		(21_948_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_ticket_naam() -> Weight {
+		(24_377_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_voorverkoop() -> Weight {
+		(22_361_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_voucher() -> Weight {
+		(23_915_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_voucher() -> Weight {
+		(26_048_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn ban_organisator() -> Weight {
+		(17_803_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unban_organisator() -> Weight {
+		(18_241_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn force_transfer_ticket() -> Weight {
+		(39_042_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn force_unscan() -> Weight {
+		(25_874_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn force_cancel_evenement(t: u32, ) -> Weight {
+		(51_337_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((1_149_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+	}
+	fn freeze_ticket() -> Weight {
+		(27_512_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unfreeze_ticket() -> Weight {
+		(24_906_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
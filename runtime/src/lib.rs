@@ -11,7 +11,11 @@ use pallet_grandpa::{
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_2, _3},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
@@ -26,12 +30,13 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	storage::{migration, unhashed},
+	traits::{ChangeMembers, KeyOwnerProofSystem, OnRuntimeUpgrade, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	StorageHasher, StorageValue, Twox128,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -101,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 125,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 13,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -270,9 +275,35 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
-impl pallet_sudo::Config for Runtime {
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+/// The council of venues that administers upticket.
+pub type CouncilCollective = pallet_collective::Instance1;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Two thirds of the council, through a motion.
+pub type EnsureCouncil =
+	pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+
+/// There is no sudo key: Root is only reachable through a motion of the council.
+impl pallet_bestuur::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
+	type RootOrigin = EnsureCouncil;
 }
 
 /// Configure the pallet-template in pallets/template.
@@ -280,9 +311,58 @@ impl pallet_sudo::Config for Runtime {
 // 	type Event = Event;
 // }
 
+parameter_types! {
+	pub const MaxScanRegistraties: u32 = 16;
+	pub const MaxNaamLengte: u32 = 64;
+	pub const MaxTekstLengte: u32 = 256;
+	pub const MaxArtiesten: u32 = 16;
+	pub const MaxCodeLengte: u32 = 32;
+	/// Enough for allowlists of over a million accounts.
+	pub const MaxBewijsLengte: u32 = 24;
+	pub const EvenementBorg: Balance = 1_000_000_000_000;
+	pub const BorgPerByte: Balance = 1_000_000_000;
+	pub const TicketBorg: Balance = 100_000_000;
+	/// Evenementen can be purged a week after they have ended.
+	pub const OpruimTermijn: u64 = 7 * 24 * 60 * 60 * 1000;
+	pub const OpruimBeloning: Perbill = Perbill::from_percent(10);
+	pub const MaxOpruimPerAanroep: u32 = 500;
+	/// Evenementen created before organizers were recorded are assigned to the first member of
+	/// the council, which [`SudoNaarCouncil`] seeds with the old sudo key.
+	pub MigratieOrganisator: Option<AccountId> = Council::members().first().cloned();
+}
+
+/// Lets the upticket benchmarks move the timestamp past the end of an evenement.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkTijd;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_upticket::BenchmarkTijd for BenchmarkTijd {
+	fn zet(millis: u64) {
+		Timestamp::set_timestamp(millis);
+	}
+}
+
 /// Configure the pallet-template in pallets/upticket.
 impl pallet_upticket::Config for Runtime {
 	type Event = Event;
+	type Tijd = Timestamp;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkTijd = BenchmarkTijd;
+	type MaxScanRegistraties = MaxScanRegistraties;
+	type MaxNaamLengte = MaxNaamLengte;
+	type MaxTekstLengte = MaxTekstLengte;
+	type MaxArtiesten = MaxArtiesten;
+	type MaxCodeLengte = MaxCodeLengte;
+	type MaxBewijsLengte = MaxBewijsLengte;
+	type Currency = Balances;
+	type EvenementBorg = EvenementBorg;
+	type BorgPerByte = BorgPerByte;
+	type TicketBorg = TicketBorg;
+	type OpruimTermijn = OpruimTermijn;
+	type OpruimBeloning = OpruimBeloning;
+	type MaxOpruimPerAanroep = MaxOpruimPerAanroep;
+	type AdminOrigin = EnsureCouncil;
+	type WeightInfo = pallet_upticket::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -299,10 +379,11 @@ construct_runtime!(
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
-		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Bestuur: pallet_bestuur::{Pallet, Call, Event<T>},
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
 		// Include the custom logic from the pallet-template in the runtime.
 		// TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
-		UpticketModule: pallet_upticket::{Pallet, Call, Storage, Event<T>},
+		UpticketModule: pallet_upticket::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -324,6 +405,42 @@ pub type SignedExtra = (
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// Hands the chain over from the sudo key to the council. Chains that ran with sudo have no
+/// council members, so the old key becomes the only member; the storage sudo left is removed.
+pub struct SudoNaarCouncil;
+
+impl OnRuntimeUpgrade for SudoNaarCouncil {
+	fn on_runtime_upgrade() -> Weight {
+		let sleutel = match migration::get_storage_value::<AccountId>(b"Sudo", b"Key", &[]) {
+			Some(sleutel) => sleutel,
+			None => return <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+		};
+
+		if Council::members().is_empty() {
+			<Council as ChangeMembers<AccountId>>::set_members_sorted(&[sleutel], &[]);
+		}
+		let _ = unhashed::kill_prefix(&Twox128::hash(b"Sudo"), None);
+
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(4, 4)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if migration::have_storage_value(b"Sudo", b"Key", &[]) {
+			Err("the sudo key was not removed")?
+		}
+		if Council::members().is_empty() {
+			Err("the council has no members")?
+		}
+		Ok(())
+	}
+}
+
+/// Storage migrations run on the next runtime upgrade, before those of the pallets.
+pub type Migrations = (
+	SudoNaarCouncil,
+	pallet_upticket::migrations::v1::MigrateToV1<Runtime, MigratieOrganisator>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -331,6 +448,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	Migrations,
 >;
 
 impl_runtime_apis! {
@@ -464,6 +582,30 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_upticket_runtime_api::UpticketApi<Block, AccountId, BlockNumber> for Runtime {
+		fn aanwezigen(evenement_id: pallet_upticket::EvenementId) -> u128 {
+			UpticketModule::aanwezigen(evenement_id)
+		}
+
+		fn beschikbaarheid(evenement_id: pallet_upticket::EvenementId) -> u128 {
+			UpticketModule::get_beschikbaarheid(evenement_id)
+		}
+
+		fn is_bevroren(
+			evenement_id: pallet_upticket::EvenementId,
+			klant_id: pallet_upticket::KlantId,
+		) -> bool {
+			UpticketModule::is_bevroren(evenement_id, klant_id)
+		}
+
+		fn scan_registraties(
+			evenement_id: pallet_upticket::EvenementId,
+			klant_id: pallet_upticket::KlantId,
+		) -> Vec<pallet_upticket::ScanRegistratie<AccountId, BlockNumber>> {
+			UpticketModule::scan_registraties(evenement_id, klant_id).into_inner()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -478,8 +620,10 @@ impl_runtime_apis! {
 
 			list_benchmark!(list, extra, frame_system, SystemBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_balances, Balances);
+			list_benchmark!(list, extra, pallet_collective, Council);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			// list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_upticket, UpticketModule);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -512,11 +656,27 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
+			add_benchmark!(params, batches, pallet_collective, Council);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			// add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_upticket, UpticketModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
 		}
 	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> (Weight, Weight) {
+			// Unwrap on purpose: a failing pre- or post-upgrade check should stop the command here,
+			// with a backtrace, instead of being reported as an ordinary error.
+			let weight = Executive::try_runtime_upgrade().unwrap();
+			(weight, BlockWeights::get().max_block)
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
 }
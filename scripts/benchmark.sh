@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Regenerates pallets/upticket/src/weights.rs. Run it on reference hardware.
+set -e
+
+cd "$(dirname "$0")/.."
+
+echo "*** Building the node with benchmarks"
+cargo build --release --features runtime-benchmarks
+
+echo "*** Benchmarking pallet_upticket"
+./target/release/node-template benchmark \
+    --chain=dev \
+    --steps=50 \
+    --repeat=20 \
+    --pallet=pallet_upticket \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --heap-pages=4096 \
+    --template=./.maintain/frame-weight-template.hbs \
+    --output=./pallets/upticket/src/weights.rs